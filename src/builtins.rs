@@ -0,0 +1,351 @@
+use super::ctx::Ctx;
+use super::exprs::*;
+use match_cast::match_cast;
+use std::io::BufRead;
+use unicode_segmentation::UnicodeSegmentation;
+
+pub fn register_defaults(ctx: &mut Ctx) {
+    ctx.register_builtin("repr", repr);
+    ctx.register_builtin("readline", readline);
+    ctx.register_builtin("eq", eq);
+    ctx.register_builtin("lt", lt);
+    ctx.register_builtin("push", push);
+    ctx.register_builtin("pop", pop);
+    ctx.register_builtin("pull", pull);
+    ctx.register_builtin("up", up);
+    ctx.register_builtin("down", down);
+    ctx.register_builtin("arr", arr);
+    ctx.register_builtin("aka", aka);
+    ctx.register_builtin("throw", throw);
+    ctx.register_builtin("call", call);
+    ctx.register_builtin("args", args_impl);
+    ctx.register_builtin("get", get);
+    ctx.register_builtin("count", count);
+    ctx.register_builtin("argc", argc);
+    ctx.register_builtin("argv", argv);
+    ctx.register_builtin("exit", exit);
+    ctx.register_builtin("die", die);
+}
+
+fn repr(_ctx: &mut Ctx, args: Vec<Expression>) -> Expression {
+    let value = match args.first() {
+        Some(value) => value,
+        None => return VoidExpr::new(),
+    };
+
+    let value = value.as_any();
+
+    match match_cast!(value {
+        _val as StringExpr => {
+            StringExpr::new("\"\"".to_string())
+        },
+        _val as NumberExpr => {
+            StringExpr::new("0".to_string())
+        },
+        _val as VoidExpr => {
+            StringExpr::new("void".to_string())
+        },
+        _val as ArrayExpr => {
+            StringExpr::new("[]".to_string())
+        },
+        _val as FunctionExpr => {
+            StringExpr::new("function () {}".to_string())
+        },
+    }) {
+        Some(expression) => expression,
+        None => VoidExpr::new(),
+    }
+}
+
+fn readline(ctx: &mut Ctx, _args: Vec<Expression>) -> Expression {
+    // Make sure any prompt has been shown before waiting on input
+    ctx.flush_output();
+
+    let mut line = String::new();
+    let stdin = std::io::stdin();
+    stdin.lock().read_line(&mut line).unwrap();
+
+    if line.ends_with("\n") {
+        line = line[0..line.len() - 1].to_string()
+    }
+
+    StringExpr::new(line)
+}
+
+fn eq(ctx: &mut Ctx, args: Vec<Expression>) -> Expression {
+    if args.len() < 2 {
+        return expected_args(ctx, &args, "eq", "at least 2");
+    }
+
+    if args.len() > 2 {
+        for i in 0..(args.len() - 1) {
+            if !CompareExpr::equals(&args[i], &args[i + 1]) {
+                return NumberExpr::new(0.0);
+            }
+        }
+        return NumberExpr::new(1.0);
+    }
+
+    NumberExpr::new(if CompareExpr::equals(&args[0], &args[1]) {
+        1.0
+    } else {
+        0.0
+    })
+}
+
+fn lt(ctx: &mut Ctx, args: Vec<Expression>) -> Expression {
+    if args.len() < 2 {
+        return expected_args(ctx, &args, "lt", "at least 2");
+    }
+
+    if args.len() > 2 {
+        for i in 0..(args.len() - 1) {
+            if !CompareExpr::less_than(&args[i], &args[i + 1]) {
+                return NumberExpr::new(0.0);
+            }
+        }
+        return NumberExpr::new(1.0);
+    }
+
+    NumberExpr::new(if CompareExpr::less_than(&args[0], &args[1]) {
+        1.0
+    } else {
+        0.0
+    })
+}
+
+fn push(ctx: &mut Ctx, args: Vec<Expression>) -> Expression {
+    match args.len() {
+        0 => {
+            ctx.push_scope(false);
+            VoidExpr::new()
+        }
+        2 => {
+            let collection = args[0].as_any();
+            let item = &args[1];
+
+            match collection.downcast_ref::<ArrayExpr>() {
+                Some(array_expr) => array_expr.value.borrow_mut().push(item.clone()),
+                None => return expected_array(ctx, "push", &args[0]),
+            }
+
+            VoidExpr::new()
+        }
+        _ => expected_args(ctx, &args, "push", "0 or 2"),
+    }
+}
+
+fn pop(ctx: &mut Ctx, args: Vec<Expression>) -> Expression {
+    match args.len() {
+        0 => {
+            ctx.pop_scope();
+            VoidExpr::new()
+        }
+        1 => {
+            let collection = args[0].as_any();
+
+            let popped = if let Some(array_expr) = collection.downcast_ref::<ArrayExpr>() {
+                array_expr.value.borrow_mut().pop()
+            } else {
+                return expected_array(ctx, "pop", &args[0]);
+            };
+
+            popped.unwrap_or_else(VoidExpr::new)
+        }
+        _ => expected_args(ctx, &args, "pop", "0 or 1"),
+    }
+}
+
+fn pull(ctx: &mut Ctx, args: Vec<Expression>) -> Expression {
+    match args.len() {
+        1 => {
+            let collection = args[0].as_any();
+
+            let popped = if let Some(array_expr) = collection.downcast_ref::<ArrayExpr>() {
+                // NOTE: Won't combine conditionals since still marked as "experimental" feature in rust
+                if !array_expr.value.borrow().is_empty() {
+                    Some(array_expr.value.borrow_mut().remove(0))
+                } else {
+                    None
+                }
+            } else {
+                return expected_array(ctx, "pull", &args[0]);
+            };
+
+            popped.unwrap_or_else(VoidExpr::new)
+        }
+        _ => expected_args(ctx, &args, "pull", "1"),
+    }
+}
+
+fn up(ctx: &mut Ctx, _args: Vec<Expression>) -> Expression {
+    ctx.up();
+    VoidExpr::new()
+}
+
+fn down(ctx: &mut Ctx, _args: Vec<Expression>) -> Expression {
+    ctx.down();
+    VoidExpr::new()
+}
+
+fn arr(_ctx: &mut Ctx, args: Vec<Expression>) -> Expression {
+    ArrayExpr::new(args, false)
+}
+
+fn aka(ctx: &mut Ctx, args: Vec<Expression>) -> Expression {
+    if args.len() < 2 {
+        return expected_args(ctx, &args, "aka", "at least 2");
+    }
+
+    for i in 0..args.len() - 1 {
+        let uid_a = uid_of(&args[i]);
+        let uid_b = uid_of(&args[i + 1]);
+
+        if uid_a.is_none() || uid_b.is_none() || uid_a.unwrap() != uid_b.unwrap() {
+            return NumberExpr::new(0.0);
+        }
+    }
+
+    NumberExpr::new(1.0)
+}
+
+fn uid_of(a: &Expression) -> Option<usize> {
+    let a_any = a.as_any();
+
+    match_cast!(a_any {
+        val as ArrayExpr => {
+            val.uid()
+        },
+        val as FunctionExpr => {
+            val.uid()
+        },
+    })
+}
+
+fn throw(ctx: &mut Ctx, args: Vec<Expression>) -> Expression {
+    let mut args = args;
+
+    ctx.throw(match args.len() {
+        1 => std::mem::replace(&mut args[0], VoidExpr::new()),
+        0 => VoidExpr::new(),
+        _ => ArrayExpr::new(args, false),
+    });
+
+    VoidExpr::new()
+}
+
+fn call(ctx: &mut Ctx, args: Vec<Expression>) -> Expression {
+    // call($f, ...) calls a function value, or a function by name
+    let mut args = args;
+
+    if args.is_empty() {
+        return expected_args(ctx, &args, "call", "at least 1");
+    }
+
+    let callee = args.remove(0);
+    ctx.call_value(&callee, args)
+}
+
+fn args_impl(ctx: &mut Ctx, _args: Vec<Expression>) -> Expression {
+    // Note that only one call to args() is allowed,
+    // Any following calls will return an empty array
+    ArrayExpr::new(std::mem::take(&mut ctx.args), false)
+}
+
+fn get(ctx: &mut Ctx, args: Vec<Expression>) -> Expression {
+    if args.len() != 2 {
+        return expected_args(ctx, &args, "get", "2");
+    }
+
+    let collection = args[0].as_any();
+
+    match_cast!(collection {
+        val as ArrayExpr => {
+            let index = NumberExpr::coerce_to_number(&args[1]);
+
+            match val.value.borrow().get(index as usize) {
+                Some(value) => value.clone(),
+                None => VoidExpr::new()
+            }
+        },
+    })
+    .unwrap_or_else(|| expected_array(ctx, "get", &args[0]))
+}
+
+fn count(ctx: &mut Ctx, args: Vec<Expression>) -> Expression {
+    if args.len() != 1 {
+        return expected_args(ctx, &args, "count", "1");
+    }
+
+    let collection = args[0].as_any();
+
+    match match_cast!( collection {
+        val as ArrayExpr => {
+            val.value.borrow().len()
+        },
+        val as StringExpr => {
+            // Counted by grapheme, the same way strings are indexed
+            val.value.graphemes(true).count()
+        },
+    }) {
+        Some(count) => NumberExpr::new(count as f64),
+        None => {
+            ctx.raise(format!("count() expects an array or string, got {}", type_name(&args[0])));
+            VoidExpr::new()
+        }
+    }
+}
+
+fn argc(ctx: &mut Ctx, _args: Vec<Expression>) -> Expression {
+    NumberExpr::new(ctx.argv.len() as f64)
+}
+
+fn argv(ctx: &mut Ctx, args: Vec<Expression>) -> Expression {
+    match args.first() {
+        // argv($i) returns a single script argument, or void when out of range
+        Some(index) => match ctx.argv.get(NumberExpr::coerce_to_number(index) as usize) {
+            Some(arg) => StringExpr::new(arg.clone()),
+            None => VoidExpr::new(),
+        },
+        None => ArrayExpr::new(
+            ctx.argv.iter().map(|arg| StringExpr::new(arg.clone())).collect(),
+            false,
+        ),
+    }
+}
+
+fn exit(ctx: &mut Ctx, args: Vec<Expression>) -> Expression {
+    match args.len() {
+        0 => ctx.exit(0),
+        1 => ctx.exit(NumberExpr::coerce_to_number(&args[0]) as i32),
+        _ => return expected_args(ctx, &args, "exit", "0 or 1"),
+    }
+
+    VoidExpr::new()
+}
+
+fn die(ctx: &mut Ctx, args: Vec<Expression>) -> Expression {
+    match args.len() {
+        0 => (),
+        1 => ctx.write_output(&format!("{}\n", args[0].stringify())),
+        _ => return expected_args(ctx, &args, "die", "0 or 1"),
+    }
+
+    ctx.exit(1);
+    VoidExpr::new()
+}
+
+fn expected_args(ctx: &mut Ctx, args: &[Expression], function: &str, expected: &str) -> Expression {
+    ctx.raise(format!(
+        "{}() expects {} arguments, got {}",
+        function,
+        expected,
+        args.len()
+    ));
+    VoidExpr::new()
+}
+
+fn expected_array(ctx: &mut Ctx, function: &str, value: &Expression) -> Expression {
+    ctx.raise(format!("{}() expects an array, got {}", function, type_name(value)));
+    VoidExpr::new()
+}
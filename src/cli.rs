@@ -0,0 +1,132 @@
+pub const USAGE: &str = "\
+Usage:
+    phpxx run <script> [-- <args>...]
+    phpxx run -e <code> [-- <args>...]
+    phpxx -e <code> [-- <args>...]
+    phpxx repl [-- <args>...]
+    phpxx check <script>
+
+Use '-' as <script> to read the script from stdin.
+Pass --lenient to turn runtime errors into void values instead of stopping the script.
+Pass --dump-tokens or --dump-ast to print how the script was lexed or parsed instead of running it,
+and add --json to get either dump as JSON.
+
+Exit status is 0 on success, 1 for runtime errors, 2 for bad usage and 3 for syntax errors.
+Scripts can exit with their own status through exit($code) and die($message).
+Arguments after '--' are available to the script through $argv, argc() and argv().";
+
+pub enum Input {
+    File(String),
+    Stdin,
+    Inline(String),
+}
+
+impl Input {
+    pub fn name(&self) -> &str {
+        match self {
+            Input::File(filename) => filename,
+            Input::Stdin => "<stdin>",
+            Input::Inline(_) => "<inline>",
+        }
+    }
+
+    pub fn read(&self) -> std::io::Result<String> {
+        match self {
+            Input::File(filename) => std::fs::read_to_string(filename),
+            Input::Stdin => {
+                let mut contents = String::new();
+                std::io::Read::read_to_string(&mut std::io::stdin(), &mut contents)?;
+                Ok(contents)
+            }
+            Input::Inline(code) => Ok(code.clone()),
+        }
+    }
+}
+
+pub enum Command {
+    Run(Input),
+    Check(Input),
+    Repl,
+    Help,
+}
+
+pub struct Options {
+    pub command: Command,
+    pub script_args: Vec<String>,
+    pub lenient: bool,
+    pub dump_tokens: bool,
+    pub dump_ast: bool,
+    pub json: bool,
+}
+
+pub fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Options, String> {
+    let mut words = vec![];
+    let mut script_args = vec![];
+    let mut lenient = false;
+    let mut dump_tokens = false;
+    let mut dump_ast = false;
+    let mut json = false;
+
+    // Flags may appear anywhere before '--', everything after it belongs to the script
+    while let Some(arg) = args.next() {
+        match &arg[..] {
+            "--" => {
+                script_args = args.collect();
+                break;
+            }
+            "--lenient" => lenient = true,
+            "--dump-tokens" => dump_tokens = true,
+            "--dump-ast" => dump_ast = true,
+            "--json" => json = true,
+            _ => words.push(arg),
+        }
+    }
+
+    let mut words = words.into_iter();
+
+    let command = match words.next().as_deref() {
+        Some("run") => Command::Run(parse_input(&mut words)?),
+        Some("-e") => Command::Run(parse_inline(&mut words)?),
+        Some("repl") => Command::Repl,
+        Some("check") => Command::Check(parse_input(&mut words)?),
+        Some("-h") | Some("--help") | Some("help") => Command::Help,
+        Some(other) => return Err(format!("Unknown command '{}'", other)),
+        None => return Err("No command given".to_string()),
+    };
+
+    if let Some(other) = words.next() {
+        return Err(format!(
+            "Unexpected argument '{}', script arguments go after '--'",
+            other
+        ));
+    }
+
+    if json && !dump_tokens && !dump_ast {
+        return Err("'--json' only applies to '--dump-tokens' and '--dump-ast'".to_string());
+    }
+
+    Ok(Options {
+        command,
+        script_args,
+        lenient,
+        dump_tokens,
+        dump_ast,
+        json,
+    })
+}
+
+fn parse_input<I: Iterator<Item = String>>(args: &mut I) -> Result<Input, String> {
+    match args.next() {
+        Some(arg) if arg == "-e" => parse_inline(args),
+        Some(arg) if arg == "-" => Ok(Input::Stdin),
+        Some(filename) => Ok(Input::File(filename)),
+        None => Err("Expected script path".to_string()),
+    }
+}
+
+fn parse_inline<I: Iterator<Item = String>>(args: &mut I) -> Result<Input, String> {
+    match args.next() {
+        Some(code) => Ok(Input::Inline(code)),
+        None => Err("Expected code after '-e'".to_string()),
+    }
+}
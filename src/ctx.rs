@@ -1,141 +1,331 @@
-use super::exprs::{Expression, VoidExpr};
+use super::builtins;
+use super::error::{Error, Span};
+use super::exprs::{type_name, ArrayExpr, Expression, FunctionDeclExpr, FunctionExpr, StringExpr, VoidExpr};
+use gc::{custom_trace, Finalize, Gc, GcCell, Trace};
+use super::output;
 use std::collections::HashMap;
+use std::io::Write;
+use std::rc::Rc;
 
-pub struct Ctx<'a> {
-    pub contents: &'a str,
+pub type Builtin = Rc<dyn Fn(&mut Ctx, Vec<Expression>) -> Expression>;
+
+pub struct Ctx {
+    pub filename: String,
+    pub contents: String,
+    pub output: Box<dyn Write>,
     pub scopes: Vec<Scope>,
     pub functions: HashMap<String, Function>,
-    pub statements: Option<&'a Vec<Expression>>,
-    pub threw: bool,
-    pub thrown: Expression,
+    pub builtins: HashMap<String, Builtin>,
+    pub signal: Option<Signal>,
     pub args: Vec<Expression>,
-    pub parsing_function: Option<usize>,
-    pub jump: Option<usize>,
+    pub argv: Vec<String>,
+    pub parse_errors: Vec<Error>,
+    pub loop_depth: usize,
+    pub error: Option<Error>,
+    pub exit_code: Option<i32>,
+    pub lenient: bool,
+}
+
+/// Control flow that cuts blocks short on its way out to whatever handles it
+pub enum Signal {
+    Throw(Expression, Option<Span>),
+    Return(Expression),
+    Break(usize),
+    Continue(usize),
 }
 
 pub struct Function {
-    address: usize,
     args: Vec<String>,
+    body: Rc<Vec<Expression>>,
 }
 
+/// Variables are shared so that closures can keep the scopes they were created in
+pub type Variables = Gc<GcCell<HashMap<String, Expression>>>;
+
+#[derive(Clone)]
 pub struct Scope {
-    pub variables: HashMap<String, Expression>,
+    pub variables: Variables,
     pub is_hard: bool,
 }
 
+impl Finalize for Scope {}
+
+unsafe impl Trace for Scope {
+    custom_trace!(this, {
+        mark(&this.variables);
+    });
+}
+
 impl Scope {
     pub fn new(is_hard: bool) -> Self {
         Self {
-            variables: HashMap::new(),
+            variables: Gc::new(GcCell::new(HashMap::new())),
             is_hard,
         }
     }
 }
 
-impl<'a> Ctx<'a> {
-    pub fn new(contents: &str) -> Ctx {
-        Ctx {
-            contents,
+impl Ctx {
+    pub fn new(filename: &str, contents: &str) -> Ctx {
+        let mut ctx = Ctx {
+            filename: filename.to_string(),
+            contents: contents.to_string(),
+            output: output::stdout(),
             scopes: vec![Scope::new(true)],
             functions: HashMap::new(),
-            statements: None,
-            threw: false,
-            thrown: VoidExpr::new(),
+            builtins: HashMap::new(),
+            signal: None,
             args: vec![],
-            parsing_function: None,
-            jump: None,
-        }
+            argv: vec![],
+            parse_errors: vec![],
+            loop_depth: 0,
+            error: None,
+            exit_code: None,
+            lenient: false,
+        };
+
+        builtins::register_defaults(&mut ctx);
+        ctx
     }
 
     pub fn prep_run(&mut self) {}
 
-    pub fn run(&mut self, statements: &'a Vec<Expression>) {
-        let mut statement_index = 0;
-        self.statements = Some(statements);
+    pub fn set_argv(&mut self, argv: Vec<String>) {
+        // Script arguments are also exposed as the global variable $argv
+        let items = argv.iter().map(|arg| StringExpr::new(arg.clone())).collect();
+        self.scopes[0]
+            .variables
+            .borrow_mut()
+            .insert("$argv".to_string(), ArrayExpr::new(items, false));
+        self.argv = argv;
+    }
 
-        while statement_index < statements.len() {
-            statements[statement_index].evaluate(self);
+    pub fn append_source(&mut self, source: &str) -> usize {
+        // Sources are appended rather than replaced so that spans
+        // from earlier inputs keep pointing at the right text
+        let offset = self.contents.len();
+        self.contents.push_str(source);
+        offset
+    }
+
+    pub fn abandon_parse(&mut self) {
+        self.parse_errors.clear();
+        self.loop_depth = 0;
+    }
+
+    pub fn run(&mut self, statements: Vec<Expression>) {
+        FunctionDeclExpr::hoist(self, &statements);
+        self.run_block(&statements);
+        self.flush_output();
+    }
 
-            if self.threw {
+    pub fn run_scoped_block(&mut self, statements: &[Expression]) {
+        // Runs a block in a transparent scope of its own. Scopes the block pushed
+        // without popping are dropped with it, including when it was cut short.
+        let depth = self.scopes.len();
+        self.push_scope(false);
+        self.run_block(statements);
+        self.scopes.truncate(depth);
+    }
+
+    pub fn run_block(&mut self, statements: &[Expression]) {
+        for statement in statements.iter() {
+            statement.evaluate(self);
+
+            if self.unwinding() {
                 break;
             }
+        }
+    }
 
-            match self.jump {
-                Some(index) => {
-                    statement_index = index;
-                    self.jump = None;
-                }
-                _ => statement_index += 1,
-            }
+    pub fn write_output(&mut self, text: &str) {
+        if self.output.write_all(text.as_bytes()).is_err() {
+            self.raise("Failed to write output".to_string());
+        }
+    }
+
+    pub fn flush_output(&mut self) {
+        // Output is buffered, so it has to be flushed before anything that
+        // the person running the script might be waiting on
+        if self.output.flush().is_err() {
+            self.raise("Failed to flush output".to_string());
+        }
+    }
+
+    pub fn register_builtin<F>(&mut self, name: &str, function: F)
+    where
+        F: Fn(&mut Ctx, Vec<Expression>) -> Expression + 'static,
+    {
+        self.builtins.insert(name.to_string(), Rc::new(function));
+    }
+
+    pub fn remove_builtin(&mut self, name: &str) -> bool {
+        self.builtins.remove(name).is_some()
+    }
+
+    pub fn call(&mut self, name: &str, args: Vec<Expression>) -> Expression {
+        // Builtins take priority over user defined functions of the same name,
+        // so scripts can't accidentally shadow the functions they rely on
+        let builtin = self.builtins.get(name).cloned();
+
+        match builtin {
+            Some(builtin) => builtin(self, args),
+            None => self.run_function(name, args),
         }
     }
 
     pub fn run_function(&mut self, name: &str, args: Vec<Expression>) -> Expression {
-        let mut statement_index;
+        let (function_args, body) = match self.functions.get(name) {
+            Some(function) => (function.args.clone(), function.body.clone()),
+            None => {
+                self.raise(format!("Undefined function '{}'", name));
+                return VoidExpr::new();
+            }
+        };
 
-        let mut function_args = match self.functions.get(name) {
-            Some(function) => {
-                statement_index = function.address;
-                function.args.clone()
-            },
+        let depth = self.scopes.len();
+        self.up();
+        let return_value = self.run_body(function_args, &body, args);
+        self.scopes.truncate(depth);
+        return_value
+    }
+
+    pub fn call_value(&mut self, callee: &Expression, args: Vec<Expression>) -> Expression {
+        // Calls a function value, strings call the function with that name
+        if let Some(name) = callee.as_any().downcast_ref::<StringExpr>() {
+            return self.call(&name.value, args);
+        }
+
+        let function = match callee.as_any().downcast_ref::<FunctionExpr>() {
+            Some(function) => function.value.clone(),
             None => {
-                return VoidExpr::new()
+                self.raise(format!("Cannot call {}", type_name(callee)));
+                return VoidExpr::new();
             }
         };
 
+        // Closures see the scopes they were created in rather than the caller's,
+        // with their arguments in a transparent scope of their own on top
+        let caller_scopes = std::mem::replace(&mut self.scopes, function.scopes.clone());
+        self.push_scope(false);
+        let return_value = self.run_body(function.args.clone(), &function.body, args);
+        self.scopes = caller_scopes;
+        return_value
+    }
+
+    fn run_body(&mut self, mut function_args: Vec<String>, statements: &[Expression], args: Vec<Expression>) -> Expression {
         // Bind function arguments to variables
-        self.up();
-        let bound = std::cmp::min(function_args.len(), args.len());
-        for i in 0..bound {
+        for value in args.iter().take(function_args.len()) {
             let name = function_args.remove(0);
-            let value = args[i].clone();
-            self.set_variable_here(name, value);
+            self.set_variable_here(name, value.clone());
         }
 
-        let statements = self.statements.unwrap();
         let previous_args = std::mem::replace(&mut self.args, args);
+        FunctionDeclExpr::hoist(self, statements);
+        self.run_block(statements);
 
-        while statement_index < statements.len() {
-            statements[statement_index].evaluate(self);
+        // Anything thrown keeps unwinding through the caller, only returns stop here
+        self.args = previous_args;
 
-            if self.threw {
-                break;
-            } else {
-                statement_index += 1;
+        match self.signal.take() {
+            Some(Signal::Return(value)) => value,
+            signal => {
+                self.signal = signal;
+                VoidExpr::new()
             }
         }
+    }
 
-        let return_value = if self.threw {
-            self.catch()
-        } else {
-            VoidExpr::new()
-        };
+    pub fn capture_scopes(&self) -> Vec<Scope> {
+        // Everything visible from here, which is every scope back to the nearest hard one
+        let start = self.scopes.iter().rposition(|scope| scope.is_hard).unwrap_or(0);
+        self.scopes[start..].to_vec()
+    }
 
-        self.args = previous_args;
-        self.down();
-        return_value
+    pub fn raise(&mut self, message: String) {
+        // Runtime errors stop execution, unless running leniently in which case
+        // the offending operation quietly evaluates to void instead
+        if !self.lenient && self.error.is_none() {
+            self.error = Some(Error::new(message, None));
+        }
+    }
+
+    pub fn locate_error(&mut self, span: &Span) {
+        // Errors and exceptions raised without a location are attributed to
+        // the innermost expression that knows its own span
+        if let Some(error) = &mut self.error {
+            if error.location.is_none() {
+                error.location = Some(span.clone());
+            }
+        }
+
+        if let Some(Signal::Throw(_, location @ None)) = &mut self.signal {
+            *location = Some(span.clone());
+        }
+    }
+
+    pub fn exit(&mut self, code: i32) {
+        if self.exit_code.is_none() {
+            self.exit_code = Some(code);
+        }
+    }
+
+    pub fn failed(&self) -> bool {
+        // Execution unwinds all the way out once a runtime error is raised or exit() is called
+        self.error.is_some() || self.exit_code.is_some()
+    }
+
+    pub fn halted(&self) -> bool {
+        // Whether the expression being evaluated should stop, which is also
+        // the case while an exception unwinds to the nearest 'catch'
+        self.failed() || self.threw()
+    }
+
+    pub fn unwinding(&self) -> bool {
+        // Whether the rest of a block should be skipped
+        self.failed() || self.signal.is_some()
+    }
+
+    pub fn threw(&self) -> bool {
+        matches!(self.signal, Some(Signal::Throw(..)))
     }
 
     pub fn throw(&mut self, value: Expression) {
-        self.threw = true;
-        self.thrown = value;
+        self.signal = Some(Signal::Throw(value, None));
     }
 
     pub fn catch(&mut self) -> Expression {
-        assert_eq!(self.threw, true);
-        self.threw = false;
-        std::mem::replace(&mut self.thrown, VoidExpr::new())
+        match self.signal.take() {
+            Some(Signal::Throw(value, _)) => value,
+            _ => panic!("Nothing was thrown"),
+        }
+    }
+
+    pub fn raise_uncaught(&mut self) {
+        // An exception that makes it out of the program is reported as a runtime error
+        // and anything else, such as a top-level return, is left for the caller
+        if !self.threw() {
+            return;
+        }
+
+        if let Some(Signal::Throw(value, location)) = self.signal.take() {
+            self.raise(format!("Uncaught exception {}", value.visualize()));
+
+            if let Some(location) = location {
+                self.locate_error(&location);
+            }
+        }
     }
 
-    pub fn add_function(&mut self, name: String, address: usize, args: Vec<String>) {
+    pub fn add_function(&mut self, name: String, args: Vec<String>, body: Rc<Vec<Expression>>) {
         // Duplicate functions will be overwritten
-        self.functions.insert(name, Function { address, args });
+        self.functions.insert(name, Function { args, body });
     }
 
     pub fn set_variable(&mut self, variable: String, value: Expression) {
         let mut depth: usize = 0;
 
-        if self.scopes.len() == 0 {
+        if self.scopes.is_empty() {
             return;
         }
 
@@ -143,8 +333,8 @@ impl<'a> Ctx<'a> {
             let scope_index = self.scopes.len() - depth - 1;
             let scope = &mut self.scopes[scope_index];
 
-            if scope.variables.get(&variable).is_some() {
-                scope.variables.insert(variable, value);
+            if let Some(existing) = scope.variables.borrow_mut().get_mut(&variable) {
+                *existing = value;
                 return;
             }
 
@@ -156,26 +346,25 @@ impl<'a> Ctx<'a> {
             }
         }
 
-        self.scopes.last_mut().unwrap().variables.insert(variable, value);
+        self.scopes.last().unwrap().variables.borrow_mut().insert(variable, value);
     }
 
     pub fn set_variable_here(&mut self, variable: String, value: Expression) {
-        self.scopes.last_mut().unwrap().variables.insert(variable, value);
+        self.scopes.last().unwrap().variables.borrow_mut().insert(variable, value);
     }
 
     pub fn get_variable(&self, variable: &str) -> Expression {
         let mut depth: usize = 0;
 
-        if self.scopes.len() == 0 {
+        if self.scopes.is_empty() {
             return VoidExpr::new();
         }
 
         loop {
             let scope = &self.scopes[self.scopes.len() - depth - 1];
 
-            match scope.variables.get(variable) {
-                Some(value) => return value.clone(),
-                _ => (),
+            if let Some(value) = scope.variables.borrow().get(variable) {
+                return value.clone();
             }
 
             if scope.is_hard {
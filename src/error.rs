@@ -1,12 +1,170 @@
+use colored::{Color, Colorize};
 pub use logos::Span;
 
+#[derive(Debug)]
 pub struct Error {
     pub message: String,
     pub location: Option<Span>,
+    pub label: Option<String>,
+    pub secondary: Vec<Label>,
+}
+
+#[derive(Debug)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
 }
 
 impl Error {
     pub fn new(message: String, location: Option<Span>) -> Error {
-        Error { message, location }
+        Error {
+            message,
+            location,
+            label: None,
+            secondary: vec![],
+        }
+    }
+
+    pub fn with_label(mut self, message: &str) -> Error {
+        // Text shown next to the carets under the primary location
+        self.label = Some(message.to_string());
+        self
+    }
+
+    pub fn with_secondary(mut self, span: Span, message: &str) -> Error {
+        self.secondary.push(Label {
+            span,
+            message: message.to_string(),
+        });
+        self
+    }
+
+    pub fn render(&self, filename: &str, contents: &str) -> String {
+        let mut result = format!("{}{}\n", "error: ".bold().red(), self.message.bold());
+
+        let location = match &self.location {
+            Some(location) => location.clone(),
+            None => return result,
+        };
+
+        // Width of the line number gutter is based on the furthest line shown
+        let last_line = std::iter::once(&location)
+            .chain(self.secondary.iter().map(|label| &label.span))
+            .map(|span| Position::of(contents, span.start).line)
+            .max()
+            .unwrap_or(1);
+        let gutter = last_line.to_string().len();
+
+        let position = Position::of(contents, location.start);
+        result.push_str(&format!(
+            "{}{} {}:{}:{}\n",
+            " ".repeat(gutter),
+            "-->".bold().blue(),
+            filename,
+            position.line,
+            position.column
+        ));
+        result.push_str(&format!("{} {}\n", " ".repeat(gutter), "|".bold().blue()));
+
+        // Labels are shown in the order they appear in the source
+        let mut labels = vec![(&location, self.label.as_deref().unwrap_or(""), '^', Color::Red)];
+        labels.extend(
+            self.secondary
+                .iter()
+                .map(|label| (&label.span, &label.message[..], '-', Color::Blue)),
+        );
+        labels.sort_by_key(|(span, _, _, _)| span.start);
+
+        let mut previous_line = None;
+
+        for (span, message, marker, color) in labels {
+            // Labels on the same line share one copy of the source line
+            let line = Position::of(contents, span.start).line;
+            let show_line = previous_line != Some(line);
+            previous_line = Some(line);
+
+            result.push_str(&render_snippet(contents, span, message, marker, color, gutter, show_line));
+        }
+
+        result
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+struct Position {
+    line: usize,
+    column: usize,
+    line_start: usize,
+}
+
+impl Position {
+    fn of(contents: &str, offset: usize) -> Position {
+        let offset = std::cmp::min(offset, contents.len());
+        let before = &contents[..offset];
+        let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+
+        Position {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            line_start,
+        }
     }
 }
+
+fn render_snippet(
+    contents: &str,
+    span: &Span,
+    message: &str,
+    marker: char,
+    color: Color,
+    gutter: usize,
+    show_line: bool,
+) -> String {
+    let position = Position::of(contents, span.start);
+    let line_end = contents[position.line_start..]
+        .find('\n')
+        .map(|index| position.line_start + index)
+        .unwrap_or_else(|| contents.len());
+    let line = &contents[position.line_start..line_end];
+
+    // Spans that cross lines are underlined up to the end of their first line,
+    // and empty spans (such as end of file) still get a single marker
+    let start = std::cmp::min(span.start, contents.len());
+    let end = std::cmp::min(std::cmp::max(span.end, start), line_end);
+    let width = std::cmp::max(contents[start..end].chars().count(), 1);
+
+    let mut result = String::new();
+
+    if show_line {
+        result.push_str(&format!(
+            "{} {} {}\n",
+            format!("{:>gutter$}", position.line, gutter = gutter).bold().blue(),
+            "|".bold().blue(),
+            line
+        ));
+    }
+
+    // Keep tabs so that the marker lines up with the source line
+    let indent: String = contents[position.line_start..start]
+        .chars()
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+
+    let underline = format!("{} {}", marker.to_string().repeat(width), message);
+    result.push_str(&format!(
+        "{} {} {}{}\n",
+        " ".repeat(gutter),
+        "|".bold().blue(),
+        indent,
+        underline.trim_end().color(color).bold()
+    ));
+
+    result
+}
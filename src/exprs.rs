@@ -1,31 +1,55 @@
 mod array;
 mod assign;
 mod call;
+mod compare;
 mod echo;
-mod jump;
+mod function;
+mod function_decl;
+mod index;
+mod index_assign;
+mod interpolate;
+mod invoke;
+mod logical;
+mod loop_control;
 mod conditional;
 mod math;
 mod number;
+mod ret;
 mod spread;
 mod string;
+mod try_catch;
+mod unary;
 mod variable;
 mod void;
 
 pub type Expression = Box<dyn Expr>;
 
-pub use super::ctx::Ctx;
+pub use super::ctx::{Ctx, Signal};
+pub use super::error::Span;
+pub use super::json;
 pub use super::lex::Token;
-pub use self::array::{ArrayValue, ArrayExpr};
-pub use self::assign::AssignExpr;
+pub use self::array::ArrayExpr;
+pub use self::assign::{AssignExpr, AssignResult};
 pub use self::call::CallExpr;
+pub use self::compare::{CompareExpr, Comparison};
 pub use self::echo::EchoExpr;
-pub use self::jump::JumpExpr;
+pub use self::function::FunctionExpr;
+pub use self::function_decl::FunctionDeclExpr;
+pub use self::index::{Index, IndexExpr};
+pub use self::index_assign::IndexAssignExpr;
+pub use self::interpolate::InterpolateExpr;
+pub use self::invoke::InvokeExpr;
+pub use self::logical::LogicalExpr;
+pub use self::loop_control::LoopControlExpr;
 pub use self::conditional::ConditionalExpr;
 pub use self::number::NumberExpr;
+pub use self::ret::ReturnExpr;
 pub use self::spread::SpreadExpr;
 pub use self::string::StringExpr;
+pub use self::try_catch::TryExpr;
 pub use self::void::VoidExpr;
-pub use self::math::MathExpr;
+pub use self::math::{MathExpr, MathOperator};
+pub use self::unary::{UnaryExpr, UnaryOperator};
 pub use self::variable::VariableExpr;
 
 use dyn_clone::DynClone;
@@ -37,10 +61,43 @@ pub trait Expr: DynClone {
     fn evaluate(&self, ctx: &mut Ctx) -> Expression;
     fn stringify(&self) -> String;
     fn visualize(&self) -> String;
+    fn jsonify(&self) -> String;
     fn plus(&self, other: &Expression) -> Expression;
     fn minus(&self, other: &Expression) -> Expression;
     fn multiply(&self, other: &Expression) -> Expression;
     fn divide(&self, other: &Expression) -> Expression;
+    fn modulo(&self, other: &Expression) -> Expression;
+    fn power(&self, other: &Expression) -> Expression;
+    fn bit_and(&self, other: &Expression) -> Expression;
+    fn bit_or(&self, other: &Expression) -> Expression;
+    fn bit_xor(&self, other: &Expression) -> Expression;
+    fn shift_left(&self, other: &Expression) -> Expression;
+    fn shift_right(&self, other: &Expression) -> Expression;
+    fn negate(&self) -> Expression;
+}
+
+pub fn type_name(value: &Expression) -> &'static str {
+    // The name of a value's type, for error messages
+    let value = value.as_any();
+
+    match_cast!(value {
+        _val as StringExpr => {
+            "string"
+        },
+        _val as NumberExpr => {
+            "number"
+        },
+        _val as VoidExpr => {
+            "void"
+        },
+        _val as ArrayExpr => {
+            "array"
+        },
+        _val as FunctionExpr => {
+            "function"
+        },
+    })
+    .unwrap_or("expression")
 }
 
 impl std::fmt::Debug for dyn Expr {
@@ -60,6 +117,10 @@ unsafe impl Trace for dyn Expr {
                 // Since Vec<T> implements Trace, the items will automatically be marked
                 mark(&val.value);
             },
+            val as FunctionExpr => {
+                // Keeps the scopes a closure captured alive for as long as the closure is
+                mark(&val.value);
+            },
         });
     });
 }
@@ -17,15 +17,51 @@ impl ArrayExpr {
     pub fn uid(&self) -> usize {
         &*self.value.borrow() as *const Vec<Expression> as usize
     }
+
+    fn items(&self) -> Vec<Expression> {
+        self.value.borrow().clone()
+    }
+
+    fn items_of(other: &Expression) -> Vec<Expression> {
+        // The items of another array, anything else is treated as an array of one item
+        match other.as_any().downcast_ref::<ArrayExpr>() {
+            Some(other) => other.items(),
+            None => vec![other.clone()],
+        }
+    }
+
+    fn contains(items: &[Expression], item: &Expression) -> bool {
+        items.iter().any(|other| CompareExpr::equals(other, item))
+    }
+
+    fn repeat(items: &[Expression], times: usize) -> Vec<Expression> {
+        let mut result = Vec::new();
+
+        for _ in 0..times {
+            result.extend(items.iter().cloned());
+        }
+        result
+    }
+
+    fn rotate(items: Vec<Expression>, amount: i64) -> Vec<Expression> {
+        // Moves 'amount' items from the start to the end, or the other way when negative
+        let mut items = items;
+
+        if !items.is_empty() {
+            let split = amount.rem_euclid(items.len() as i64) as usize;
+            items.rotate_left(split);
+        }
+        items
+    }
 }
 
 impl Expr for ArrayExpr {
     fn as_any(&self) -> &dyn std::any::Any {
-        return self
+        self
     }
 
     fn evaluate(&self, _ctx: &mut Ctx) -> Expression {
-        return Box::new(Self { value: self.value.clone(), spread: self.spread });
+        Box::new(Self { value: self.value.clone(), spread: self.spread })
     }
 
     fn stringify(&self) -> String {
@@ -36,19 +72,143 @@ impl Expr for ArrayExpr {
         format!("[{}]", self.value.borrow().iter().map(|x| x.visualize()).collect::<Vec<String>>().join(", "))
     }
 
+    fn jsonify(&self) -> String {
+        let items: Vec<String> = self.value.borrow().iter().map(|x| x.jsonify()).collect();
+        json::object(&[
+            ("type", json::string("Array")),
+            ("items", json::array(&items)),
+        ])
+    }
+
     fn plus(&self, _other: &Expression) -> Expression {
+        // Arrays can't be added to, MathExpr::apply reports the void as an error
         VoidExpr::new()
     }
-    
-    fn minus(&self, _other: &Expression) -> Expression {
-        VoidExpr::new()
+
+    fn minus(&self, other: &Expression) -> Expression {
+        // Drops items from the end, or keeps only the last ones reversed when negative,
+        // the same as for strings
+        let items = self.items();
+        let amount = NumberExpr::to_integer(NumberExpr::coerce_to_number(other));
+
+        let result = if amount < 0 {
+            items.into_iter().rev().take(amount.unsigned_abs() as usize).collect()
+        } else {
+            let keep = items.len().saturating_sub(amount as usize);
+            items.into_iter().take(keep).collect()
+        };
+
+        ArrayExpr::new(result, false)
     }
 
-    fn multiply(&self, _other: &Expression) -> Expression {
-        VoidExpr::new()
+    fn multiply(&self, other: &Expression) -> Expression {
+        // Repeats the items, reversing them first when negative
+        let mut items = self.items();
+        let count = NumberExpr::to_integer(NumberExpr::coerce_to_number(other));
+
+        if count < 0 {
+            items.reverse();
+        }
+
+        ArrayExpr::new(Self::repeat(&items, count.unsigned_abs() as usize), false)
     }
-    
+
     fn divide(&self, _other: &Expression) -> Expression {
+        // Arrays can't be divided, MathExpr::apply reports the void as an error
         VoidExpr::new()
     }
+
+    fn modulo(&self, other: &Expression) -> Expression {
+        // Whatever is left over after dividing: the items that don't fit into chunks
+        // of a given size, or the array with every item equal to the other value taken out
+        let items = self.items();
+
+        if !other.as_any().is::<NumberExpr>() {
+            let result = items.into_iter().filter(|item| !CompareExpr::equals(item, other)).collect();
+            return ArrayExpr::new(result, false);
+        }
+
+        let size = NumberExpr::to_integer(NumberExpr::coerce_to_number(other)).unsigned_abs() as usize;
+
+        if size == 0 {
+            return ArrayExpr::new(items, false);
+        }
+
+        let leftover = items.len() % size;
+        ArrayExpr::new(items[items.len() - leftover..].to_vec(), false)
+    }
+
+    fn power(&self, other: &Expression) -> Expression {
+        // Repeats every item a number of times, or puts another value in between the items
+        let items = self.items();
+
+        if other.as_any().is::<NumberExpr>() {
+            let count = NumberExpr::to_integer(NumberExpr::coerce_to_number(other)).max(0) as usize;
+            let result = items.iter().flat_map(|item| Self::repeat(std::slice::from_ref(item), count)).collect();
+            return ArrayExpr::new(result, false);
+        }
+
+        let mut result = Vec::new();
+
+        for (i, item) in items.into_iter().enumerate() {
+            if i > 0 {
+                result.push(other.clone());
+            }
+            result.push(item);
+        }
+
+        ArrayExpr::new(result, false)
+    }
+
+    fn bit_and(&self, other: &Expression) -> Expression {
+        // Items that appear in both arrays
+        let others = Self::items_of(other);
+        let result = self.items().into_iter().filter(|item| Self::contains(&others, item)).collect();
+
+        ArrayExpr::new(result, false)
+    }
+
+    fn bit_or(&self, other: &Expression) -> Expression {
+        // The items followed by the items of the other array it doesn't have yet
+        let mut items = self.items();
+        let extra: Vec<Expression> = Self::items_of(other)
+            .into_iter()
+            .filter(|item| !Self::contains(&items, item))
+            .collect();
+
+        items.extend(extra);
+        ArrayExpr::new(items, false)
+    }
+
+    fn bit_xor(&self, other: &Expression) -> Expression {
+        // Items that appear in only one of the arrays
+        let own = self.items();
+        let others = Self::items_of(other);
+
+        let result = own
+            .iter()
+            .filter(|item| !Self::contains(&others, item))
+            .chain(others.iter().filter(|item| !Self::contains(&own, item)))
+            .cloned()
+            .collect();
+
+        ArrayExpr::new(result, false)
+    }
+
+    fn shift_left(&self, other: &Expression) -> Expression {
+        // Rotates the items, so arr(1, 2, 3) << 1 is [2, 3, 1]
+        let amount = NumberExpr::to_integer(NumberExpr::coerce_to_number(other));
+        ArrayExpr::new(Self::rotate(self.items(), amount), false)
+    }
+
+    fn shift_right(&self, other: &Expression) -> Expression {
+        let amount = NumberExpr::to_integer(NumberExpr::coerce_to_number(other));
+        ArrayExpr::new(Self::rotate(self.items(), amount.saturating_neg()), false)
+    }
+
+    fn negate(&self) -> Expression {
+        // A reversed copy, the original array is left as it is
+        let items = self.value.borrow().iter().rev().cloned().collect();
+        ArrayExpr::new(items, false)
+    }
 }
@@ -2,13 +2,39 @@ use super::*;
 
 pub struct AssignExpr {
     pub variable: String,
-    pub value: Expression
+    pub operator: Option<MathOperator>,
+    pub value: Expression,
+    pub result: AssignResult,
+    pub span: Span,
+}
+
+/// What an assignment evaluates to, only increments and decrements give a value
+#[derive(Clone, Copy, PartialEq)]
+pub enum AssignResult {
+    Void,
+    NewValue,
+    OldValue,
 }
 
 impl AssignExpr {
-    #[allow(dead_code)]
-    pub fn new(variable: String, value: Expression) -> Expression {
-        Box::new(Self { variable, value })
+    pub fn new(variable: String, value: Expression, span: Span) -> Expression {
+        Box::new(Self { variable, operator: None, value, result: AssignResult::Void, span })
+    }
+
+    pub fn compound(variable: String, operator: MathOperator, value: Expression, span: Span) -> Expression {
+        // '$a += 1' and friends, where 'operator' is the operator without the '='
+        Box::new(Self { variable, operator: Some(operator), value, result: AssignResult::Void, span })
+    }
+
+    pub fn increment(variable: String, operator: MathOperator, prefix: bool, span: Span) -> Expression {
+        // '++$a' and '$a--' and friends, where 'operator' is either '+' or '-'
+        let result = if prefix {
+            AssignResult::NewValue
+        } else {
+            AssignResult::OldValue
+        };
+
+        Box::new(Self { variable, operator: Some(operator), value: NumberExpr::new(1.0), result, span })
     }
 }
 
@@ -18,9 +44,35 @@ impl Expr for AssignExpr {
     }
 
     fn evaluate(&self, ctx: &mut Ctx) -> Expression {
+        // The current value is looked up the same way it is set, so updating a variable
+        // from inside a transparent scope changes the variable outside of it
+        let old = match (self.operator, self.result) {
+            (None, AssignResult::Void) => VoidExpr::new(),
+            _ => ctx.get_variable(&self.variable),
+        };
+
         let value = self.value.evaluate(ctx);
-        ctx.set_variable(self.variable.to_string(), value);
-        VoidExpr::new()
+
+        if ctx.halted() {
+            return VoidExpr::new();
+        }
+
+        let new = match self.operator {
+            Some(operator) => MathExpr::apply(ctx, &old, operator, &value, &self.span),
+            None => value,
+        };
+
+        if ctx.halted() {
+            return VoidExpr::new();
+        }
+
+        ctx.set_variable(self.variable.to_string(), new.clone());
+
+        match self.result {
+            AssignResult::Void => VoidExpr::new(),
+            AssignResult::NewValue => new,
+            AssignResult::OldValue => old,
+        }
     }
 
     fn stringify(&self) -> String {
@@ -28,7 +80,30 @@ impl Expr for AssignExpr {
     }
 
     fn visualize(&self) -> String {
-        format!("{} = {}", self.variable, self.value.visualize()) 
+        let operator = self.operator.map_or("", |operator| operator.symbol());
+
+        match self.result {
+            AssignResult::Void => format!("{} {}= {}", self.variable, operator, self.value.visualize()),
+            AssignResult::NewValue => format!("({}{}{})", operator, operator, self.variable),
+            AssignResult::OldValue => format!("({}{}{})", self.variable, operator, operator),
+        }
+    }
+
+    fn jsonify(&self) -> String {
+        let result = match self.result {
+            AssignResult::Void => "void",
+            AssignResult::NewValue => "new",
+            AssignResult::OldValue => "old",
+        };
+
+        json::object(&[
+            ("type", json::string("Assign")),
+            ("variable", json::string(&self.variable)),
+            ("operator", self.operator.map_or("null".to_string(), |operator| json::string(operator.symbol()))),
+            ("value", self.value.jsonify()),
+            ("result", json::string(result)),
+            ("span", json::span(&self.span)),
+        ])
     }
 
     fn plus(&self, _other: &Expression) -> Expression {
@@ -46,13 +121,48 @@ impl Expr for AssignExpr {
     fn divide(&self, _other: &Expression) -> Expression {
         VoidExpr::new()
     }
+
+    fn modulo(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn power(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_and(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_or(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_xor(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_left(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_right(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn negate(&self) -> Expression {
+        VoidExpr::new()
+    }
 }
 
 impl Clone for AssignExpr {
     fn clone(&self) -> Self {
         Self {
             variable: self.variable.clone(),
-            value: dyn_clone::clone_box(&*self.value)
+            operator: self.operator,
+            value: dyn_clone::clone_box(&*self.value),
+            result: self.result,
+            span: self.span.clone(),
         }
     }
 }
@@ -0,0 +1,229 @@
+use super::*;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    Greater,
+    LessEqual,
+    GreaterEqual,
+}
+
+impl Comparison {
+    pub fn from_token(token: &Token) -> Option<Comparison> {
+        match token {
+            Token::Equal => Some(Comparison::Equal),
+            Token::NotEqual => Some(Comparison::NotEqual),
+            Token::Less => Some(Comparison::Less),
+            Token::Greater => Some(Comparison::Greater),
+            Token::LessEqual => Some(Comparison::LessEqual),
+            Token::GreaterEqual => Some(Comparison::GreaterEqual),
+            _ => None,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Comparison::Equal => "==",
+            Comparison::NotEqual => "!=",
+            Comparison::Less => "<",
+            Comparison::Greater => ">",
+            Comparison::LessEqual => "<=",
+            Comparison::GreaterEqual => ">=",
+        }
+    }
+}
+
+pub struct CompareExpr {
+    pub lhs: Expression,
+    pub operator: Comparison,
+    pub rhs: Expression,
+    pub span: Span,
+}
+
+impl CompareExpr {
+    pub fn new(lhs: Expression, operator: Comparison, rhs: Expression, span: Span) -> Expression {
+        Box::new(Self { lhs, operator, rhs, span })
+    }
+
+    pub fn equals(a: &Expression, b: &Expression) -> bool {
+        let a_any = a.as_any();
+
+        match_cast!(a_any {
+            val as StringExpr => {
+                val.value == StringExpr::coerce_to_string(b)
+            },
+            val as NumberExpr => {
+                val.value == NumberExpr::coerce_to_number(b)
+            },
+            _val as VoidExpr => {
+                b.as_any().is::<VoidExpr>()
+            },
+            val as ArrayExpr => {
+                if let Some(other) = b.as_any().downcast_ref::<ArrayExpr>() {
+                    Self::equals_array(val, other)
+                } else {
+                    false
+                }
+            },
+            val as FunctionExpr => {
+                // Functions are only equal to themselves, like arrays with the same uid
+                if let Some(other) = b.as_any().downcast_ref::<FunctionExpr>() {
+                    val.uid() == other.uid()
+                } else {
+                    false
+                }
+            },
+        })
+        .unwrap_or(false)
+    }
+
+    fn equals_array(a: &ArrayExpr, b: &ArrayExpr) -> bool {
+        if a.uid() == b.uid() {
+            // Same uid means same array and so equal
+            return true;
+        }
+
+        // Strip off unnecessary abstraction, leave only &Vec<Expression> left
+        let a = a.value.borrow();
+        let b = b.value.borrow();
+
+        if a.len() != b.len() {
+            return false;
+        }
+
+        for i in 0..a.len() {
+            if !Self::equals(&a[i], &b[i]) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    pub fn less_than(a: &Expression, b: &Expression) -> bool {
+        let a_any = a.as_any();
+
+        match_cast!(a_any {
+            val as StringExpr => {
+                val.value < StringExpr::coerce_to_string(b)
+            },
+            val as NumberExpr => {
+                val.value < NumberExpr::coerce_to_number(b)
+            },
+            _val as VoidExpr => {
+                0.0 < NumberExpr::coerce_to_number(b)
+            },
+            val as ArrayExpr => {
+                (val.value.borrow().len() as f64) < NumberExpr::coerce_to_number(b)
+            },
+        })
+        .unwrap_or(false)
+    }
+}
+
+impl Expr for CompareExpr {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn evaluate(&self, ctx: &mut Ctx) -> Expression {
+        let l = self.lhs.evaluate(ctx);
+        let r = self.rhs.evaluate(ctx);
+
+        if ctx.halted() {
+            return VoidExpr::new();
+        }
+
+        // Like the math operators, the left operand decides how the right one is coerced,
+        // so every comparison is worked out from '==' and '<' with the operands in order
+        let result = match self.operator {
+            Comparison::Equal => Self::equals(&l, &r),
+            Comparison::NotEqual => !Self::equals(&l, &r),
+            Comparison::Less => Self::less_than(&l, &r),
+            Comparison::Greater => !Self::less_than(&l, &r) && !Self::equals(&l, &r),
+            Comparison::LessEqual => Self::less_than(&l, &r) || Self::equals(&l, &r),
+            Comparison::GreaterEqual => !Self::less_than(&l, &r),
+        };
+
+        NumberExpr::new(if result { 1.0 } else { 0.0 })
+    }
+
+    fn stringify(&self) -> String {
+        panic!();
+    }
+
+    fn visualize(&self) -> String {
+        format!("({} {} {})", self.lhs.visualize(), self.operator.symbol(), self.rhs.visualize())
+    }
+
+    fn jsonify(&self) -> String {
+        json::object(&[
+            ("type", json::string("Compare")),
+            ("operator", json::string(self.operator.symbol())),
+            ("lhs", self.lhs.jsonify()),
+            ("rhs", self.rhs.jsonify()),
+            ("span", json::span(&self.span)),
+        ])
+    }
+
+    fn plus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn minus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn multiply(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn divide(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn modulo(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn power(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_and(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_or(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_xor(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_left(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_right(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn negate(&self) -> Expression {
+        VoidExpr::new()
+    }
+}
+
+impl Clone for CompareExpr {
+    fn clone(&self) -> Self {
+        Self {
+            lhs: self.lhs.clone(),
+            operator: self.operator,
+            rhs: self.rhs.clone(),
+            span: self.span.clone(),
+        }
+    }
+}
@@ -22,12 +22,16 @@ impl ConditionalExpr {
         })
     }
 
-    pub fn visualize_block(block: &Vec<Expression>) -> String {
+    pub fn visualize_block(block: &[Expression]) -> String {
         let mut result = String::new();
 
+        // Nested blocks are indented so the structure of the tree stays readable
         for i in block.iter() {
-            result.push_str(&i.visualize());
-            result.push_str("\n");
+            for line in i.visualize().lines() {
+                result.push_str("    ");
+                result.push_str(line);
+                result.push('\n');
+            }
         }
 
         result
@@ -36,7 +40,7 @@ impl ConditionalExpr {
 
 impl Expr for ConditionalExpr {
     fn as_any(&self) -> &dyn std::any::Any {
-        return self;
+        self
     }
 
     fn evaluate(&self, ctx: &mut Ctx) -> Expression {
@@ -44,6 +48,11 @@ impl Expr for ConditionalExpr {
 
         loop  {
             let is_true = NumberExpr::is_true(&self.condition.evaluate(ctx));
+
+            if ctx.halted() {
+                break;
+            }
+
             let chosen_statements = if is_true {
                 Some(&self.when_true)
             } else {
@@ -54,17 +63,23 @@ impl Expr for ConditionalExpr {
                 }
             };
 
-            ctx.push_scope(false);
+            if let Some(chosen_statements) = chosen_statements {
+                ctx.run_scoped_block(chosen_statements);
+            }
 
-            if chosen_statements.is_some() {
-                for statement in chosen_statements.unwrap().iter() {
-                    statement.evaluate(ctx);
+            if self.is_while && is_true {
+                // A depth of 1 is meant for this loop, deeper ones are passed on to the loop around it
+                match ctx.signal.take() {
+                    Some(Signal::Continue(1)) => (),
+                    Some(Signal::Break(1)) => break,
+                    Some(Signal::Break(depth)) => ctx.signal = Some(Signal::Break(depth - 1)),
+                    Some(Signal::Continue(depth)) => ctx.signal = Some(Signal::Continue(depth - 1)),
+                    signal => ctx.signal = signal,
                 }
             }
 
-            ctx.pop_scope();
-
-            if !self.is_while || !is_true {
+            // Anything else that cuts the block short leaves the loop without checking the condition again
+            if !self.is_while || !is_true || ctx.unwinding() {
                 break;
             }
 
@@ -85,13 +100,41 @@ impl Expr for ConditionalExpr {
             "if"
         };
 
-        format!(
-            "{} {} {{\n{}}} else {{\n{}}}",
+        let mut result = format!(
+            "{} {} {{\n{}}}",
             name,
             self.condition.visualize(),
-            ConditionalExpr::visualize_block(&self.when_true),
-            ConditionalExpr::visualize_block(&self.when_false)
-        )
+            ConditionalExpr::visualize_block(&self.when_true)
+        );
+
+        // An 'else' block holding nothing but another 'if' is shown as 'else if',
+        // so chains stay flat instead of nesting deeper with every branch
+        let chained = match &self.when_false[..] {
+            [only] => only.as_any().downcast_ref::<ConditionalExpr>().filter(|only| !only.is_while),
+            _ => None,
+        };
+
+        match chained {
+            Some(chained) => result.push_str(&format!(" else {}", chained.visualize())),
+            None if !self.when_false.is_empty() => result.push_str(&format!(
+                " else {{\n{}}}",
+                ConditionalExpr::visualize_block(&self.when_false)
+            )),
+            None => (),
+        }
+
+        result
+    }
+
+    fn jsonify(&self) -> String {
+        let when_true: Vec<String> = self.when_true.iter().map(|x| x.jsonify()).collect();
+        let when_false: Vec<String> = self.when_false.iter().map(|x| x.jsonify()).collect();
+        json::object(&[
+            ("type", json::string(if self.is_while { "While" } else { "If" })),
+            ("condition", self.condition.jsonify()),
+            ("when_true", json::array(&when_true)),
+            ("when_false", json::array(&when_false)),
+        ])
     }
 
     fn plus(&self, _other: &Expression) -> Expression {
@@ -107,6 +150,38 @@ impl Expr for ConditionalExpr {
     fn divide(&self, _other: &Expression) -> Expression {
         VoidExpr::new()
     }
+
+    fn modulo(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn power(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_and(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_or(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_xor(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_left(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_right(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn negate(&self) -> Expression {
+        VoidExpr::new()
+    }
 }
 
 impl Clone for ConditionalExpr {
@@ -1,5 +1,4 @@
 use super::*;
-use std::io::Write;
 
 pub struct EchoExpr {
     pub value: Expression,
@@ -20,13 +19,16 @@ impl Expr for EchoExpr {
     fn evaluate(&self, ctx: &mut Ctx) -> Expression {
         let message = self.value.evaluate(ctx).stringify();
 
+        if ctx.halted() {
+            return VoidExpr::new();
+        }
+
         if self.newline {
-            println!("{}", message);
+            ctx.write_output(&format!("{}\n", message));
         } else {
-            print!("{}", message);
-            std::io::stdout().flush().unwrap();
+            ctx.write_output(&message);
         }
-        
+
         VoidExpr::new()
     }
 
@@ -42,6 +44,14 @@ impl Expr for EchoExpr {
         }
     }
 
+    fn jsonify(&self) -> String {
+        json::object(&[
+            ("type", json::string("Echo")),
+            ("newline", self.newline.to_string()),
+            ("value", self.value.jsonify()),
+        ])
+    }
+
     fn plus(&self, _other: &Expression) -> Expression {
         VoidExpr::new()
     }
@@ -57,6 +67,38 @@ impl Expr for EchoExpr {
     fn divide(&self, _other: &Expression) -> Expression {
         VoidExpr::new()
     }
+
+    fn modulo(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn power(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_and(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_or(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_xor(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_left(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_right(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn negate(&self) -> Expression {
+        VoidExpr::new()
+    }
 }
 
 impl Clone for EchoExpr {
@@ -0,0 +1,121 @@
+use gc::{custom_trace, Finalize, Gc, Trace};
+use super::*;
+use super::super::ctx::Scope;
+use std::rc::Rc;
+
+pub struct FunctionValue {
+    pub args: Vec<String>,
+    pub body: Rc<Vec<Expression>>,
+    pub scopes: Vec<Scope>,
+}
+
+impl Finalize for FunctionValue {}
+
+unsafe impl Trace for FunctionValue {
+    custom_trace!(this, {
+        // The body is never changed after parsing, so only the captured scopes hold values
+        mark(&this.scopes);
+    });
+}
+
+#[derive(Clone)]
+pub struct FunctionExpr {
+    pub value: Gc<FunctionValue>,
+}
+
+impl FunctionExpr {
+    pub fn new(args: Vec<String>, body: Rc<Vec<Expression>>, scopes: Vec<Scope>) -> Expression {
+        Box::new(Self {
+            value: Gc::new(FunctionValue { args, body, scopes }),
+        })
+    }
+
+    pub fn uid(&self) -> usize {
+        &*self.value as *const FunctionValue as usize
+    }
+
+    pub fn visualize_function(name: Option<&str>, args: &[String], body: &[Expression]) -> String {
+        let name = match name {
+            Some(name) => format!("function {}", name),
+            None => String::from("function "),
+        };
+
+        format!("{}({}) {{\n{}}}", name, args.join(", "), ConditionalExpr::visualize_block(body))
+    }
+}
+
+impl Expr for FunctionExpr {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn evaluate(&self, _ctx: &mut Ctx) -> Expression {
+        Box::new(self.clone())
+    }
+
+    fn stringify(&self) -> String {
+        self.visualize()
+    }
+
+    fn visualize(&self) -> String {
+        FunctionExpr::visualize_function(None, &self.value.args, &self.value.body)
+    }
+
+    fn jsonify(&self) -> String {
+        let args: Vec<String> = self.value.args.iter().map(|x| json::string(x)).collect();
+        let body: Vec<String> = self.value.body.iter().map(|x| x.jsonify()).collect();
+        json::object(&[
+            ("type", json::string("FunctionValue")),
+            ("args", json::array(&args)),
+            ("body", json::array(&body)),
+        ])
+    }
+
+    fn plus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn minus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn multiply(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn divide(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn modulo(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn power(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_and(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_or(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_xor(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_left(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_right(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn negate(&self) -> Expression {
+        VoidExpr::new()
+    }
+}
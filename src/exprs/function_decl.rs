@@ -0,0 +1,115 @@
+use super::*;
+use std::rc::Rc;
+
+#[derive(Clone)]
+pub struct FunctionDeclExpr {
+    pub name: Option<String>,
+    pub args: Vec<String>,
+    pub body: Rc<Vec<Expression>>,
+}
+
+impl FunctionDeclExpr {
+    pub fn new(name: Option<String>, args: Vec<String>, body: Vec<Expression>) -> Expression {
+        Box::new(Self {
+            name,
+            args,
+            body: Rc::new(body),
+        })
+    }
+
+    pub fn hoist(ctx: &mut Ctx, statements: &[Expression]) {
+        // Functions declared directly in a program or function body can be called
+        // before their declaration, ones inside blocks exist once the block runs
+        for statement in statements.iter() {
+            if let Some(function) = statement.as_any().downcast_ref::<FunctionDeclExpr>() {
+                if function.name.is_some() {
+                    function.evaluate(ctx);
+                }
+            }
+        }
+    }
+}
+
+impl Expr for FunctionDeclExpr {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn evaluate(&self, ctx: &mut Ctx) -> Expression {
+        // Anonymous functions are values, named ones are declarations
+        match &self.name {
+            Some(name) => {
+                ctx.add_function(name.clone(), self.args.clone(), self.body.clone());
+                VoidExpr::new()
+            }
+            None => FunctionExpr::new(self.args.clone(), self.body.clone(), ctx.capture_scopes()),
+        }
+    }
+
+    fn stringify(&self) -> String {
+        panic!();
+    }
+
+    fn visualize(&self) -> String {
+        FunctionExpr::visualize_function(self.name.as_deref(), &self.args, &self.body)
+    }
+
+    fn jsonify(&self) -> String {
+        let args: Vec<String> = self.args.iter().map(|x| json::string(x)).collect();
+        let body: Vec<String> = self.body.iter().map(|x| x.jsonify()).collect();
+        json::object(&[
+            ("type", json::string("Function")),
+            ("name", self.name.as_ref().map_or("null".to_string(), |name| json::string(name))),
+            ("args", json::array(&args)),
+            ("body", json::array(&body)),
+        ])
+    }
+
+    fn plus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn minus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn multiply(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn divide(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn modulo(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn power(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_and(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_or(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_xor(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_left(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_right(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn negate(&self) -> Expression {
+        VoidExpr::new()
+    }
+}
@@ -0,0 +1,241 @@
+use super::*;
+use unicode_segmentation::UnicodeSegmentation;
+
+pub struct IndexExpr {
+    pub value: Expression,
+    pub index: Index,
+    pub span: Span,
+}
+
+/// What goes between the brackets, either a single position or a range of them
+#[derive(Clone)]
+pub enum Index {
+    At(Expression),
+    Slice(Option<Expression>, Option<Expression>),
+}
+
+impl IndexExpr {
+    pub fn new(value: Expression, index: Index, span: Span) -> Expression {
+        Box::new(Self { value, index, span })
+    }
+
+    pub fn evaluate_index(ctx: &mut Ctx, index: &Expression, span: &Span) -> Option<i64> {
+        // Indexes must be numbers, and are truncated toward zero
+        let index = index.evaluate(ctx);
+
+        if ctx.halted() {
+            return None;
+        }
+
+        match index.as_any().downcast_ref::<NumberExpr>() {
+            Some(number) => Some(NumberExpr::to_integer(number.value)),
+            None => {
+                ctx.raise(format!("Index must be a number, got {}", type_name(&index)));
+                ctx.locate_error(span);
+                None
+            }
+        }
+    }
+
+    pub fn position(index: i64, length: usize) -> Option<usize> {
+        // Negative indexes count back from the end, so -1 is the last item
+        let position = if index < 0 {
+            length as i64 + index
+        } else {
+            index
+        };
+
+        if position >= 0 && (position as usize) < length {
+            Some(position as usize)
+        } else {
+            None
+        }
+    }
+
+    fn slice_bounds(ctx: &mut Ctx, start: &Option<Expression>, end: &Option<Expression>, length: usize, span: &Span) -> Option<(usize, usize)> {
+        // Slices include the start but not the end, and are clamped to the items there are
+        let mut bound = |bound: &Option<Expression>, default: usize| -> Option<usize> {
+            let index = match bound {
+                Some(bound) => Self::evaluate_index(ctx, bound, span)?,
+                None => return Some(default),
+            };
+
+            let position = if index < 0 { length as i64 + index } else { index };
+            Some(position.clamp(0, length as i64) as usize)
+        };
+
+        let start = bound(start, 0)?;
+        let end = bound(end, length)?;
+        Some((start, std::cmp::max(start, end)))
+    }
+
+    fn out_of_range(ctx: &mut Ctx, index: i64, value: &Expression, length: usize, span: &Span) -> Expression {
+        ctx.raise(format!("Index {} is out of range for {} of length {}", index, type_name(value), length));
+        ctx.locate_error(span);
+        VoidExpr::new()
+    }
+}
+
+impl Expr for IndexExpr {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn evaluate(&self, ctx: &mut Ctx) -> Expression {
+        let value = self.value.evaluate(ctx);
+
+        if ctx.halted() {
+            return VoidExpr::new();
+        }
+
+        // Strings are indexed by grapheme, the same way they are reversed
+        if let Some(string) = value.as_any().downcast_ref::<StringExpr>() {
+            let graphemes: Vec<&str> = string.value.graphemes(true).collect();
+
+            return match &self.index {
+                Index::At(index) => {
+                    let index = match Self::evaluate_index(ctx, index, &self.span) {
+                        Some(index) => index,
+                        None => return VoidExpr::new(),
+                    };
+
+                    match Self::position(index, graphemes.len()) {
+                        Some(position) => StringExpr::new(graphemes[position].to_string()),
+                        None => Self::out_of_range(ctx, index, &value, graphemes.len(), &self.span),
+                    }
+                }
+                Index::Slice(start, end) => match Self::slice_bounds(ctx, start, end, graphemes.len(), &self.span) {
+                    Some((start, end)) => StringExpr::new(graphemes[start..end].concat()),
+                    None => VoidExpr::new(),
+                },
+            };
+        }
+
+        let array = match value.as_any().downcast_ref::<ArrayExpr>() {
+            Some(array) => array.value.clone(),
+            None => {
+                ctx.raise(format!("Cannot index into {}", type_name(&value)));
+                ctx.locate_error(&self.span);
+                return VoidExpr::new();
+            }
+        };
+
+        let length = array.borrow().len();
+
+        match &self.index {
+            Index::At(index) => {
+                let index = match Self::evaluate_index(ctx, index, &self.span) {
+                    Some(index) => index,
+                    None => return VoidExpr::new(),
+                };
+
+                match Self::position(index, length) {
+                    Some(position) => array.borrow()[position].clone(),
+                    None => Self::out_of_range(ctx, index, &value, length, &self.span),
+                }
+            }
+            Index::Slice(start, end) => match Self::slice_bounds(ctx, start, end, length, &self.span) {
+                // Slices are copies, changing them leaves the original array alone
+                Some((start, end)) => ArrayExpr::new(array.borrow()[start..end].to_vec(), false),
+                None => VoidExpr::new(),
+            },
+        }
+    }
+
+    fn stringify(&self) -> String {
+        panic!();
+    }
+
+    fn visualize(&self) -> String {
+        format!("{}[{}]", self.value.visualize(), self.index.visualize())
+    }
+
+    fn jsonify(&self) -> String {
+        let index = match &self.index {
+            Index::At(index) => index.jsonify(),
+            Index::Slice(start, end) => json::object(&[
+                ("type", json::string("Slice")),
+                ("start", start.as_ref().map_or("null".to_string(), |start| start.jsonify())),
+                ("end", end.as_ref().map_or("null".to_string(), |end| end.jsonify())),
+            ]),
+        };
+
+        json::object(&[
+            ("type", json::string("Index")),
+            ("value", self.value.jsonify()),
+            ("index", index),
+            ("span", json::span(&self.span)),
+        ])
+    }
+
+    fn plus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn minus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn multiply(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn divide(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn modulo(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn power(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_and(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_or(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_xor(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_left(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_right(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn negate(&self) -> Expression {
+        VoidExpr::new()
+    }
+}
+
+impl Index {
+    pub fn visualize(&self) -> String {
+        match self {
+            Index::At(index) => index.visualize(),
+            Index::Slice(start, end) => format!(
+                "{}..{}",
+                start.as_ref().map_or(String::new(), |start| start.visualize()),
+                end.as_ref().map_or(String::new(), |end| end.visualize())
+            ),
+        }
+    }
+}
+
+impl Clone for IndexExpr {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            index: self.index.clone(),
+            span: self.span.clone(),
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use super::*;
+
+pub struct IndexAssignExpr {
+    pub target: Expression,
+    pub index: Option<Expression>,
+    pub value: Expression,
+    pub span: Span,
+}
+
+impl IndexAssignExpr {
+    pub fn new(target: Expression, index: Option<Expression>, value: Expression, span: Span) -> Expression {
+        // A missing index, as in '$a[] = 1', appends to the array
+        Box::new(Self { target, index, value, span })
+    }
+}
+
+impl Expr for IndexAssignExpr {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn evaluate(&self, ctx: &mut Ctx) -> Expression {
+        let target = self.target.evaluate(ctx);
+        let value = self.value.evaluate(ctx);
+
+        if ctx.halted() {
+            return VoidExpr::new();
+        }
+
+        let array = match target.as_any().downcast_ref::<ArrayExpr>() {
+            Some(array) => array.value.clone(),
+            None => {
+                // Appending to a variable that hasn't been set yet starts a new array
+                if let (None, true, Some(variable)) = (
+                    &self.index,
+                    target.as_any().is::<VoidExpr>(),
+                    self.target.as_any().downcast_ref::<VariableExpr>(),
+                ) {
+                    ctx.set_variable(variable.name.clone(), ArrayExpr::new(vec![value], false));
+                    return VoidExpr::new();
+                }
+
+                ctx.raise(format!("Cannot assign to an index of {}", type_name(&target)));
+                ctx.locate_error(&self.span);
+                return VoidExpr::new();
+            }
+        };
+
+        let index = match &self.index {
+            Some(index) => match IndexExpr::evaluate_index(ctx, index, &self.span) {
+                Some(index) => index,
+                None => return VoidExpr::new(),
+            },
+            None => {
+                array.borrow_mut().push(value);
+                return VoidExpr::new();
+            }
+        };
+
+        // Arrays are changed in place, so every alias of the array sees the new item.
+        // Assigning just past the end appends, anything further out is an error.
+        let length = array.borrow().len();
+
+        match IndexExpr::position(index, length) {
+            Some(position) => array.borrow_mut()[position] = value,
+            None if index == length as i64 => array.borrow_mut().push(value),
+            None => {
+                ctx.raise(format!("Index {} is out of range for array of length {}", index, length));
+                ctx.locate_error(&self.span);
+            }
+        }
+
+        VoidExpr::new()
+    }
+
+    fn stringify(&self) -> String {
+        panic!();
+    }
+
+    fn visualize(&self) -> String {
+        format!(
+            "{}[{}] = {}",
+            self.target.visualize(),
+            self.index.as_ref().map_or(String::new(), |index| index.visualize()),
+            self.value.visualize()
+        )
+    }
+
+    fn jsonify(&self) -> String {
+        json::object(&[
+            ("type", json::string("IndexAssign")),
+            ("target", self.target.jsonify()),
+            ("index", self.index.as_ref().map_or("null".to_string(), |index| index.jsonify())),
+            ("value", self.value.jsonify()),
+            ("span", json::span(&self.span)),
+        ])
+    }
+
+    fn plus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn minus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn multiply(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn divide(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn modulo(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn power(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_and(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_or(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_xor(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_left(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_right(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn negate(&self) -> Expression {
+        VoidExpr::new()
+    }
+}
+
+impl Clone for IndexAssignExpr {
+    fn clone(&self) -> Self {
+        Self {
+            target: self.target.clone(),
+            index: self.index.clone(),
+            value: self.value.clone(),
+            span: self.span.clone(),
+        }
+    }
+}
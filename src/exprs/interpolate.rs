@@ -0,0 +1,119 @@
+use super::*;
+
+pub struct InterpolateExpr {
+    pub parts: Vec<Expression>,
+}
+
+impl InterpolateExpr {
+    pub fn new(parts: Vec<Expression>) -> Expression {
+        Box::new(Self { parts })
+    }
+}
+
+impl Expr for InterpolateExpr {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn evaluate(&self, ctx: &mut Ctx) -> Expression {
+        // Parts are joined with the same coercions as adding them to a string
+        let mut result = StringExpr::new(String::new());
+
+        for part in self.parts.iter() {
+            let value = part.evaluate(ctx);
+
+            if ctx.halted() {
+                return VoidExpr::new();
+            }
+
+            result = result.plus(&value);
+        }
+
+        result
+    }
+
+    fn stringify(&self) -> String {
+        panic!();
+    }
+
+    fn visualize(&self) -> String {
+        let mut result = String::from("\"");
+
+        for part in self.parts.iter() {
+            match part.as_any().downcast_ref::<StringExpr>() {
+                Some(string) => {
+                    let inside = string.visualize();
+                    result.push_str(&inside[1..inside.len() - 1]);
+                }
+                None => result.push_str(&format!("{{{}}}", part.visualize())),
+            }
+        }
+
+        result.push('"');
+        result
+    }
+
+    fn jsonify(&self) -> String {
+        let parts: Vec<String> = self.parts.iter().map(|x| x.jsonify()).collect();
+        json::object(&[
+            ("type", json::string("Interpolate")),
+            ("parts", json::array(&parts)),
+        ])
+    }
+
+    fn plus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn minus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn multiply(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn divide(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn modulo(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn power(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_and(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_or(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_xor(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_left(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_right(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn negate(&self) -> Expression {
+        VoidExpr::new()
+    }
+}
+
+impl Clone for InterpolateExpr {
+    fn clone(&self) -> Self {
+        Self {
+            parts: self.parts.clone(),
+        }
+    }
+}
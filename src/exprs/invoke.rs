@@ -0,0 +1,110 @@
+use super::*;
+
+pub struct InvokeExpr {
+    pub callee: Expression,
+    pub args: Vec<Expression>,
+    pub span: Span,
+}
+
+impl InvokeExpr {
+    pub fn new(callee: Expression, args: Vec<Expression>, span: Span) -> Expression {
+        // Calls whatever function value the callee evaluates to, as in '$f(1, 2)'
+        Box::new(Self { callee, args, span })
+    }
+}
+
+impl Expr for InvokeExpr {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn evaluate(&self, ctx: &mut Ctx) -> Expression {
+        let callee = self.callee.evaluate(ctx);
+        let args = CallExpr::evaluate_args(ctx, &self.args);
+
+        if ctx.halted() {
+            return VoidExpr::new();
+        }
+
+        let result = ctx.call_value(&callee, args);
+        ctx.locate_error(&self.span);
+        result
+    }
+
+    fn stringify(&self) -> String {
+        panic!();
+    }
+
+    fn visualize(&self) -> String {
+        let args: Vec<String> = self.args.iter().map(|x| x.visualize()).collect();
+        format!("{}({})", self.callee.visualize(), args.join(", "))
+    }
+
+    fn jsonify(&self) -> String {
+        let args: Vec<String> = self.args.iter().map(|x| x.jsonify()).collect();
+        json::object(&[
+            ("type", json::string("Invoke")),
+            ("callee", self.callee.jsonify()),
+            ("args", json::array(&args)),
+            ("span", json::span(&self.span)),
+        ])
+    }
+
+    fn plus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn minus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn multiply(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn divide(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn modulo(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn power(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_and(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_or(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_xor(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_left(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_right(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn negate(&self) -> Expression {
+        VoidExpr::new()
+    }
+}
+
+impl Clone for InvokeExpr {
+    fn clone(&self) -> Self {
+        Self {
+            callee: self.callee.clone(),
+            args: self.args.clone(),
+            span: self.span.clone(),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use super::*;
+
+pub struct LogicalExpr {
+    pub lhs: Expression,
+    pub operator: Token,
+    pub rhs: Expression,
+    pub span: Span,
+}
+
+impl LogicalExpr {
+    pub fn new(lhs: Expression, operator: &Token, rhs: Expression, span: Span) -> Expression {
+        Box::new(Self { lhs, operator: *operator, rhs, span })
+    }
+}
+
+impl Expr for LogicalExpr {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn evaluate(&self, ctx: &mut Ctx) -> Expression {
+        let l = NumberExpr::is_true(&self.lhs.evaluate(ctx));
+
+        if ctx.halted() {
+            return VoidExpr::new();
+        }
+
+        // The right operand is only evaluated when it can change the result
+        let result = match (self.operator, l) {
+            (Token::And, false) => false,
+            (Token::Or, true) => true,
+            _ => NumberExpr::is_true(&self.rhs.evaluate(ctx)),
+        };
+
+        if ctx.halted() {
+            return VoidExpr::new();
+        }
+
+        NumberExpr::new(if result { 1.0 } else { 0.0 })
+    }
+
+    fn stringify(&self) -> String {
+        panic!();
+    }
+
+    fn visualize(&self) -> String {
+        format!("({} {} {})", self.lhs.visualize(), self.operator.symbol(), self.rhs.visualize())
+    }
+
+    fn jsonify(&self) -> String {
+        json::object(&[
+            ("type", json::string("Logical")),
+            ("operator", json::string(self.operator.symbol())),
+            ("lhs", self.lhs.jsonify()),
+            ("rhs", self.rhs.jsonify()),
+            ("span", json::span(&self.span)),
+        ])
+    }
+
+    fn plus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn minus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn multiply(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn divide(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn modulo(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn power(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_and(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_or(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_xor(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_left(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_right(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn negate(&self) -> Expression {
+        VoidExpr::new()
+    }
+}
+
+impl Clone for LogicalExpr {
+    fn clone(&self) -> Self {
+        Self {
+            lhs: self.lhs.clone(),
+            operator: self.operator,
+            rhs: self.rhs.clone(),
+            span: self.span.clone(),
+        }
+    }
+}
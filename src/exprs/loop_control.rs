@@ -0,0 +1,98 @@
+use super::*;
+
+#[derive(Clone)]
+pub struct LoopControlExpr {
+    pub depth: usize,
+    pub is_continue: bool,
+}
+
+impl LoopControlExpr {
+    pub fn new(depth: usize, is_continue: bool) -> Expression {
+        Box::new(Self { depth, is_continue })
+    }
+}
+
+impl Expr for LoopControlExpr {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn evaluate(&self, ctx: &mut Ctx) -> Expression {
+        ctx.signal = Some(if self.is_continue {
+            Signal::Continue(self.depth)
+        } else {
+            Signal::Break(self.depth)
+        });
+
+        VoidExpr::new()
+    }
+
+    fn stringify(&self) -> String {
+        panic!();
+    }
+
+    fn visualize(&self) -> String {
+        let name = if self.is_continue { "continue" } else { "break" };
+
+        if self.depth == 1 {
+            name.to_string()
+        } else {
+            format!("{} {}", name, self.depth)
+        }
+    }
+
+    fn jsonify(&self) -> String {
+        json::object(&[
+            ("type", json::string(if self.is_continue { "Continue" } else { "Break" })),
+            ("depth", self.depth.to_string()),
+        ])
+    }
+
+    fn plus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn minus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn multiply(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn divide(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn modulo(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn power(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_and(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_or(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_xor(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_left(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_right(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn negate(&self) -> Expression {
+        VoidExpr::new()
+    }
+}
@@ -1,34 +1,108 @@
 use super::*;
 
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MathOperator {
+    Plus,
+    Minus,
+    Multiply,
+    Divide,
+    Modulo,
+    Power,
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
+}
+
+impl MathOperator {
+    pub fn from_token(token: &Token) -> Option<MathOperator> {
+        match token {
+            Token::Plus => Some(MathOperator::Plus),
+            Token::Minus => Some(MathOperator::Minus),
+            Token::Multiply => Some(MathOperator::Multiply),
+            Token::Divide => Some(MathOperator::Divide),
+            Token::Modulo => Some(MathOperator::Modulo),
+            Token::Power => Some(MathOperator::Power),
+            Token::BitAnd => Some(MathOperator::BitAnd),
+            Token::BitOr => Some(MathOperator::BitOr),
+            Token::BitXor => Some(MathOperator::BitXor),
+            Token::ShiftLeft => Some(MathOperator::ShiftLeft),
+            Token::ShiftRight => Some(MathOperator::ShiftRight),
+            _ => None,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            MathOperator::Plus => "+",
+            MathOperator::Minus => "-",
+            MathOperator::Multiply => "*",
+            MathOperator::Divide => "/",
+            MathOperator::Modulo => "%",
+            MathOperator::Power => "**",
+            MathOperator::BitAnd => "&",
+            MathOperator::BitOr => "|",
+            MathOperator::BitXor => "^",
+            MathOperator::ShiftLeft => "<<",
+            MathOperator::ShiftRight => ">>",
+        }
+    }
+}
+
 pub struct MathExpr {
     pub lhs: Expression,
-    pub operator: Token,
+    pub operator: MathOperator,
     pub rhs: Expression,
+    pub span: Span,
 }
 
 impl MathExpr {
-    pub fn new(lhs: Expression, operator: &Token, rhs: Expression) -> Expression {
-        Box::new(Self { lhs, operator: *operator, rhs })
+    pub fn new(lhs: Expression, operator: MathOperator, rhs: Expression, span: Span) -> Expression {
+        Box::new(Self { lhs, operator, rhs, span })
+    }
+
+    pub fn apply(ctx: &mut Ctx, l: &Expression, operator: MathOperator, r: &Expression, span: &Span) -> Expression {
+        // Applies an operator to values that have already been evaluated,
+        // 'span' is where the operator was written
+        let result = match operator {
+            MathOperator::Plus => l.plus(r),
+            MathOperator::Minus => l.minus(r),
+            MathOperator::Multiply => l.multiply(r),
+            MathOperator::Divide => l.divide(r),
+            MathOperator::Modulo => l.modulo(r),
+            MathOperator::Power => l.power(r),
+            MathOperator::BitAnd => l.bit_and(r),
+            MathOperator::BitOr => l.bit_or(r),
+            MathOperator::BitXor => l.bit_xor(r),
+            MathOperator::ShiftLeft => l.shift_left(r),
+            MathOperator::ShiftRight => l.shift_right(r),
+        };
+
+        // Arrays give back void for the operators they don't have, which is an error
+        if l.as_any().is::<ArrayExpr>() && result.as_any().is::<VoidExpr>() {
+            ctx.raise(format!(
+                "Cannot use array as left operand of '{}'",
+                &ctx.contents[span.clone()]
+            ));
+            ctx.locate_error(span);
+            return VoidExpr::new();
+        }
+
+        result
     }
 }
 
 impl Expr for MathExpr {
     fn as_any(&self) -> &dyn std::any::Any {
-        return self
+        self
     }
 
     fn evaluate(&self, ctx: &mut Ctx) -> Expression {
         let l = self.lhs.evaluate(ctx);
         let r = self.rhs.evaluate(ctx);
-        match self.operator {
-            Token::Plus => l.plus(&r),
-            Token::Minus => l.minus(&r),
-            Token::Multiply => l.multiply(&r),
-            Token::Divide => l.divide(&r),
-            _ => {
-                unimplemented!();
-            }
-        }
+
+        MathExpr::apply(ctx, &l, self.operator, &r, &self.span)
     }
 
     fn stringify(&self) -> String {
@@ -36,12 +110,17 @@ impl Expr for MathExpr {
     }
 
     fn visualize(&self) -> String {
-        let binary_op = match self.operator {
-            Token::Plus => "+",
-            Token::Multiply => "*",
-            _ => "<?>"
-        };
-        format!("({} {} {})", self.lhs.visualize(), binary_op, self.rhs.visualize())
+        format!("({} {} {})", self.lhs.visualize(), self.operator.symbol(), self.rhs.visualize())
+    }
+
+    fn jsonify(&self) -> String {
+        json::object(&[
+            ("type", json::string("Math")),
+            ("operator", json::string(self.operator.symbol())),
+            ("lhs", self.lhs.jsonify()),
+            ("rhs", self.rhs.jsonify()),
+            ("span", json::span(&self.span)),
+        ])
     }
 
     fn plus(&self, _other: &Expression) -> Expression {
@@ -59,6 +138,38 @@ impl Expr for MathExpr {
     fn divide(&self, _other: &Expression) -> Expression {
         VoidExpr::new()
     }
+
+    fn modulo(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn power(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_and(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_or(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_xor(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_left(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_right(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn negate(&self) -> Expression {
+        VoidExpr::new()
+    }
 }
 
 impl Clone for MathExpr {
@@ -67,6 +178,7 @@ impl Clone for MathExpr {
             lhs: self.lhs.clone(),
             operator: self.operator,
             rhs: self.rhs.clone(),
+            span: self.span.clone(),
         }
     }
 }
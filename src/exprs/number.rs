@@ -28,9 +28,26 @@ impl NumberExpr {
         }).unwrap_or(0.0)
     }
 
+    pub fn to_integer(value: f64) -> i64 {
+        // Integer operators truncate toward zero, turn NaN into 0 and clamp
+        // anything out of range to the smallest or largest 64-bit integer
+        value as i64
+    }
+
+    fn shift(value: i64, amount: i64) -> i64 {
+        // Shifts left by 'amount' bits, negative amounts shift right keeping the sign,
+        // and shifting by 64 bits or more shifts every bit out
+        match amount {
+            amount if amount >= 64 => 0,
+            amount if amount >= 0 => value << amount,
+            amount if amount > -64 => value >> -amount,
+            _ => if value < 0 { -1 } else { 0 },
+        }
+    }
+
     pub fn is_true(other: &Expression) -> bool {
         let number = Self::coerce_to_number(other);
-        return number != 0.0
+        number != 0.0
     }
 }
 
@@ -51,6 +68,13 @@ impl Expr for NumberExpr {
         self.value.to_string()
     }
 
+    fn jsonify(&self) -> String {
+        json::object(&[
+            ("type", json::string("Number")),
+            ("value", json::number(self.value)),
+        ])
+    }
+
     fn plus(&self, other: &Expression) -> Expression {
         NumberExpr::new(self.value + Self::coerce_to_number(other))
     }
@@ -66,4 +90,45 @@ impl Expr for NumberExpr {
     fn divide(&self, other: &Expression) -> Expression {
         NumberExpr::new(self.value / Self::coerce_to_number(other))
     }
+
+    fn modulo(&self, other: &Expression) -> Expression {
+        // The remainder has the sign of the left operand, and is NaN for a divisor of zero
+        let divisor = Self::to_integer(Self::coerce_to_number(other));
+
+        NumberExpr::new(match Self::to_integer(self.value).checked_rem(divisor) {
+            Some(remainder) => remainder as f64,
+            None if divisor == 0 => f64::NAN,
+            None => 0.0,
+        })
+    }
+
+    fn power(&self, other: &Expression) -> Expression {
+        NumberExpr::new(self.value.powf(Self::coerce_to_number(other)))
+    }
+
+    fn bit_and(&self, other: &Expression) -> Expression {
+        NumberExpr::new((Self::to_integer(self.value) & Self::to_integer(Self::coerce_to_number(other))) as f64)
+    }
+
+    fn bit_or(&self, other: &Expression) -> Expression {
+        NumberExpr::new((Self::to_integer(self.value) | Self::to_integer(Self::coerce_to_number(other))) as f64)
+    }
+
+    fn bit_xor(&self, other: &Expression) -> Expression {
+        NumberExpr::new((Self::to_integer(self.value) ^ Self::to_integer(Self::coerce_to_number(other))) as f64)
+    }
+
+    fn shift_left(&self, other: &Expression) -> Expression {
+        let amount = Self::to_integer(Self::coerce_to_number(other));
+        NumberExpr::new(Self::shift(Self::to_integer(self.value), amount) as f64)
+    }
+
+    fn shift_right(&self, other: &Expression) -> Expression {
+        let amount = Self::to_integer(Self::coerce_to_number(other));
+        NumberExpr::new(Self::shift(Self::to_integer(self.value), amount.saturating_neg()) as f64)
+    }
+
+    fn negate(&self) -> Expression {
+        NumberExpr::new(-self.value)
+    }
 }
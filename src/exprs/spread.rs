@@ -12,7 +12,7 @@ impl SpreadExpr {
 
 impl Expr for SpreadExpr {
     fn as_any(&self) -> &dyn std::any::Any {
-        return self
+        self
     }
 
     fn evaluate(&self, ctx: &mut Ctx) -> Expression {
@@ -34,6 +34,13 @@ impl Expr for SpreadExpr {
         format!(".. ({})", self.value.visualize())
     }
 
+    fn jsonify(&self) -> String {
+        json::object(&[
+            ("type", json::string("Spread")),
+            ("value", self.value.jsonify()),
+        ])
+    }
+
     fn plus(&self, _other: &Expression) -> Expression {
         VoidExpr::new()
     }
@@ -49,6 +56,38 @@ impl Expr for SpreadExpr {
     fn divide(&self, _other: &Expression) -> Expression {
         VoidExpr::new()
     }
+
+    fn modulo(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn power(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_and(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_or(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_xor(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_left(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_right(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn negate(&self) -> Expression {
+        VoidExpr::new()
+    }
 }
 
 impl Clone for SpreadExpr {
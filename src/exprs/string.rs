@@ -27,14 +27,29 @@ impl StringExpr {
             val as ArrayExpr => {
                 val.stringify()
             },
+            val as FunctionExpr => {
+                val.stringify()
+            },
         })
         .unwrap_or(String::from(""))
     }
 
+    fn rotate(string: &str, amount: i64) -> String {
+        // Moves 'amount' graphemes from the start to the end, or the other way when negative
+        let graphemes: Vec<&str> = string.graphemes(true).collect();
+
+        if graphemes.is_empty() {
+            return String::new();
+        }
+
+        let split = amount.rem_euclid(graphemes.len() as i64) as usize;
+        format!("{}{}", graphemes[split..].concat(), graphemes[..split].concat())
+    }
+
     pub fn multiply_string(string: &str, times: usize) -> String {
         let mut result = String::new();
 
-        for _ in 0..(times as usize) {
+        for _ in 0..times {
             result.push_str(string);
         }
         result
@@ -55,19 +70,37 @@ impl Expr for StringExpr {
     }
 
     fn visualize(&self) -> String {
-        let inside = snailquote::escape(&self.value);
-
-        if inside.starts_with("\"") {
-            format!("{}", inside)
-        } else if inside.starts_with("\'") {
-            format!("\"{}\"", &inside[1..inside.len() - 1])
-        } else {
-            format!("\"{}\"", inside)
+        // Escaped so that the result reads back as the same string,
+        // including the characters that would start an interpolation
+        let mut result = String::from("\"");
+
+        for c in self.value.chars() {
+            match c {
+                '"' => result.push_str("\\\""),
+                '\\' => result.push_str("\\\\"),
+                '\n' => result.push_str("\\n"),
+                '\t' => result.push_str("\\t"),
+                '\r' => result.push_str("\\r"),
+                '$' => result.push_str("\\$"),
+                '{' => result.push_str("\\{"),
+                c if c.is_control() => result.push_str(&format!("\\u{{{:x}}}", c as u32)),
+                c => result.push(c),
+            }
         }
+
+        result.push('"');
+        result
+    }
+
+    fn jsonify(&self) -> String {
+        json::object(&[
+            ("type", json::string("String")),
+            ("value", json::string(&self.value)),
+        ])
     }
 
     fn plus(&self, other: &Expression) -> Expression {
-        return StringExpr::new(format!("{}{}", self.value, Self::coerce_to_string(other)));
+        StringExpr::new(format!("{}{}", self.value, Self::coerce_to_string(other)))
     }
     fn minus(&self, original: &Expression) -> Expression {
         let other = original.as_any();
@@ -80,7 +113,7 @@ impl Expr for StringExpr {
                 let amount = NumberExpr::coerce_to_number(original) as i64;
 
                 let result = if amount < 0 {
-                    (&self.value).graphemes(true).rev().take((amount * -1) as usize).collect()
+                    self.value.graphemes(true).rev().take(-amount as usize).collect()
                 } else if (amount as usize) < self.value.len() {
                     self.value[0..(self.value.len() - amount as usize)].to_string()
                 } else {
@@ -92,19 +125,19 @@ impl Expr for StringExpr {
         });
 
         match value {
-            Some(value) => return value,
-            None => return StringExpr::new("".to_string()),
+            Some(value) => value,
+            None => StringExpr::new("".to_string()),
         }
     }
     fn multiply(&self, other: &Expression) -> Expression {
         let count = NumberExpr::coerce_to_number(other) as i64;
-        return StringExpr::new(if count < 0 {
-            let seed: String = (&self.value).graphemes(true).rev().collect();
-            let count = count.checked_neg().unwrap_or(std::i64::MAX);
+        StringExpr::new(if count < 0 {
+            let seed: String = self.value.graphemes(true).rev().collect();
+            let count = count.checked_neg().unwrap_or(i64::MAX);
             Self::multiply_string(&seed, count as usize)
         } else {
             Self::multiply_string(&self.value, count as usize)
-        });
+        })
     }
 
     fn divide(&self, other: &Expression) -> Expression {
@@ -125,4 +158,87 @@ impl Expr for StringExpr {
             None => NumberExpr::new(0.0),
         }
     }
+
+    fn modulo(&self, other: &Expression) -> Expression {
+        // Whatever is left over after dividing: the string with every match of another
+        // string taken out, or the graphemes that don't fit into chunks of a given size
+        if let Some(other) = other.as_any().downcast_ref::<StringExpr>() {
+            if other.value.is_empty() {
+                return StringExpr::new(self.value.clone());
+            }
+
+            return StringExpr::new(self.value.replace(&other.value, ""));
+        }
+
+        let graphemes: Vec<&str> = self.value.graphemes(true).collect();
+        let size = NumberExpr::to_integer(NumberExpr::coerce_to_number(other)).unsigned_abs() as usize;
+
+        if size == 0 {
+            return StringExpr::new(self.value.clone());
+        }
+
+        StringExpr::new(graphemes[graphemes.len() - graphemes.len() % size..].concat())
+    }
+
+    fn power(&self, other: &Expression) -> Expression {
+        // Joins the graphemes with another string in between them,
+        // or repeats every grapheme a number of times
+        let graphemes: Vec<&str> = self.value.graphemes(true).collect();
+
+        if let Some(other) = other.as_any().downcast_ref::<StringExpr>() {
+            return StringExpr::new(graphemes.join(&other.value));
+        }
+
+        let count = NumberExpr::to_integer(NumberExpr::coerce_to_number(other)).max(0) as usize;
+        StringExpr::new(graphemes.iter().map(|grapheme| Self::multiply_string(grapheme, count)).collect())
+    }
+
+    fn bit_and(&self, other: &Expression) -> Expression {
+        // Graphemes that appear in both strings
+        let other = Self::coerce_to_string(other);
+        let other: Vec<&str> = other.graphemes(true).collect();
+
+        StringExpr::new(self.value.graphemes(true).filter(|grapheme| other.contains(grapheme)).collect())
+    }
+
+    fn bit_or(&self, other: &Expression) -> Expression {
+        // The string followed by the graphemes of the other string it doesn't have yet
+        let other = Self::coerce_to_string(other);
+        let own: Vec<&str> = self.value.graphemes(true).collect();
+        let extra: String = other.graphemes(true).filter(|grapheme| !own.contains(grapheme)).collect();
+
+        StringExpr::new(format!("{}{}", self.value, extra))
+    }
+
+    fn bit_xor(&self, other: &Expression) -> Expression {
+        // Graphemes that appear in only one of the strings
+        let other = Self::coerce_to_string(other);
+        let own: Vec<&str> = self.value.graphemes(true).collect();
+        let others: Vec<&str> = other.graphemes(true).collect();
+
+        let result: String = own
+            .iter()
+            .filter(|grapheme| !others.contains(grapheme))
+            .chain(others.iter().filter(|grapheme| !own.contains(grapheme)))
+            .copied()
+            .collect();
+
+        StringExpr::new(result)
+    }
+
+    fn shift_left(&self, other: &Expression) -> Expression {
+        // Rotates the graphemes, so "abc" << 1 is "bca"
+        let amount = NumberExpr::to_integer(NumberExpr::coerce_to_number(other));
+        StringExpr::new(Self::rotate(&self.value, amount))
+    }
+
+    fn shift_right(&self, other: &Expression) -> Expression {
+        let amount = NumberExpr::to_integer(NumberExpr::coerce_to_number(other));
+        StringExpr::new(Self::rotate(&self.value, amount.saturating_neg()))
+    }
+
+    fn negate(&self) -> Expression {
+        // Reversed by grapheme, the same as multiplying by -1
+        StringExpr::new(self.value.graphemes(true).rev().collect())
+    }
 }
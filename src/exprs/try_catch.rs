@@ -0,0 +1,135 @@
+use super::*;
+
+#[derive(Clone)]
+pub struct TryExpr {
+    pub body: Vec<Expression>,
+    pub catch: Option<(String, Vec<Expression>)>,
+    pub finally: Option<Vec<Expression>>,
+}
+
+impl TryExpr {
+    pub fn new(
+        body: Vec<Expression>,
+        catch: Option<(String, Vec<Expression>)>,
+        finally: Option<Vec<Expression>>,
+    ) -> Expression {
+        Box::new(Self { body, catch, finally })
+    }
+
+    fn run_finally(ctx: &mut Ctx, finally: &[Expression]) {
+        // Whatever was thrown or returned is put aside while the 'finally' block runs,
+        // and carries on afterwards unless the block throws or returns something of its own
+        let pending = ctx.signal.take();
+        ctx.run_scoped_block(finally);
+
+        if !ctx.unwinding() {
+            ctx.signal = pending;
+        }
+    }
+}
+
+impl Expr for TryExpr {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn evaluate(&self, ctx: &mut Ctx) -> Expression {
+        ctx.run_scoped_block(&self.body);
+
+        if let (true, Some((variable, when_caught))) = (ctx.threw(), &self.catch) {
+            // The caught value is only visible inside the 'catch' block
+            let depth = ctx.scopes.len();
+            let value = ctx.catch();
+
+            ctx.push_scope(false);
+            ctx.set_variable_here(variable.clone(), value);
+            ctx.run_block(when_caught);
+            ctx.scopes.truncate(depth);
+        }
+
+        // Runtime errors and exit() end the script without running 'finally' blocks
+        if let (false, Some(finally)) = (ctx.failed(), &self.finally) {
+            Self::run_finally(ctx, finally);
+        }
+
+        VoidExpr::new()
+    }
+
+    fn stringify(&self) -> String {
+        panic!();
+    }
+
+    fn visualize(&self) -> String {
+        let mut result = format!("try {{\n{}}}", ConditionalExpr::visualize_block(&self.body));
+
+        if let Some((variable, when_caught)) = &self.catch {
+            result.push_str(&format!(" catch ({}) {{\n{}}}", variable, ConditionalExpr::visualize_block(when_caught)));
+        }
+
+        if let Some(finally) = &self.finally {
+            result.push_str(&format!(" finally {{\n{}}}", ConditionalExpr::visualize_block(finally)));
+        }
+
+        result
+    }
+
+    fn jsonify(&self) -> String {
+        let block = |block: &[Expression]| json::array(&block.iter().map(|x| x.jsonify()).collect::<Vec<String>>());
+
+        json::object(&[
+            ("type", json::string("Try")),
+            ("body", block(&self.body)),
+            ("variable", self.catch.as_ref().map_or("null".to_string(), |(variable, _)| json::string(variable))),
+            ("when_caught", self.catch.as_ref().map_or("null".to_string(), |(_, when_caught)| block(when_caught))),
+            ("finally", self.finally.as_ref().map_or("null".to_string(), |finally| block(finally))),
+        ])
+    }
+
+    fn plus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn minus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn multiply(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn divide(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn modulo(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn power(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_and(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_or(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_xor(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_left(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_right(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn negate(&self) -> Expression {
+        VoidExpr::new()
+    }
+}
@@ -0,0 +1,125 @@
+use super::*;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum UnaryOperator {
+    Not,
+    Negate,
+    Identity,
+}
+
+impl UnaryOperator {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            UnaryOperator::Not => "!",
+            UnaryOperator::Negate => "-",
+            UnaryOperator::Identity => "+",
+        }
+    }
+}
+
+pub struct UnaryExpr {
+    pub operator: UnaryOperator,
+    pub value: Expression,
+    pub span: Span,
+}
+
+impl UnaryExpr {
+    pub fn new(operator: UnaryOperator, value: Expression, span: Span) -> Expression {
+        Box::new(Self { operator, value, span })
+    }
+}
+
+impl Expr for UnaryExpr {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn evaluate(&self, ctx: &mut Ctx) -> Expression {
+        let value = self.value.evaluate(ctx);
+
+        if ctx.halted() {
+            return VoidExpr::new();
+        }
+
+        match self.operator {
+            UnaryOperator::Not => NumberExpr::new(if NumberExpr::is_true(&value) { 0.0 } else { 1.0 }),
+            UnaryOperator::Negate => value.negate(),
+            UnaryOperator::Identity => value,
+        }
+    }
+
+    fn stringify(&self) -> String {
+        panic!();
+    }
+
+    fn visualize(&self) -> String {
+        format!("({}{})", self.operator.symbol(), self.value.visualize())
+    }
+
+    fn jsonify(&self) -> String {
+        json::object(&[
+            ("type", json::string("Unary")),
+            ("operator", json::string(self.operator.symbol())),
+            ("value", self.value.jsonify()),
+            ("span", json::span(&self.span)),
+        ])
+    }
+
+    fn plus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn minus(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn multiply(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn divide(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn modulo(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn power(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_and(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_or(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_xor(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_left(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_right(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn negate(&self) -> Expression {
+        VoidExpr::new()
+    }
+}
+
+impl Clone for UnaryExpr {
+    fn clone(&self) -> Self {
+        Self {
+            operator: self.operator,
+            value: self.value.clone(),
+            span: self.span.clone(),
+        }
+    }
+}
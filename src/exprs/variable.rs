@@ -13,7 +13,7 @@ impl VariableExpr {
 
 impl Expr for VariableExpr {
     fn as_any(&self) -> &dyn std::any::Any {
-        return self
+        self
     }
 
     fn evaluate(&self, ctx: &mut Ctx) -> Expression {
@@ -28,6 +28,13 @@ impl Expr for VariableExpr {
         self.name.clone()
     }
 
+    fn jsonify(&self) -> String {
+        json::object(&[
+            ("type", json::string("Variable")),
+            ("name", json::string(&self.name)),
+        ])
+    }
+
     fn plus(&self, _other: &Expression) -> Expression {
         VoidExpr::new()
     }
@@ -43,4 +50,36 @@ impl Expr for VariableExpr {
     fn divide(&self, _other: &Expression) -> Expression {
         VoidExpr::new()
     }
+
+    fn modulo(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn power(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_and(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_or(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_xor(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_left(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_right(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn negate(&self) -> Expression {
+        VoidExpr::new()
+    }
 }
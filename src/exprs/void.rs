@@ -11,7 +11,7 @@ impl VoidExpr {
 
 impl Expr for VoidExpr {
     fn as_any(&self) -> &dyn std::any::Any {
-        return self
+        self
     }
 
     fn evaluate(&self, _ctx: &mut Ctx) -> Expression {
@@ -26,6 +26,10 @@ impl Expr for VoidExpr {
         String::from("void")
     }
 
+    fn jsonify(&self) -> String {
+        json::object(&[("type", json::string("Void"))])
+    }
+
     fn plus(&self, _other: &Expression) -> Expression {
         VoidExpr::new()
     }
@@ -41,10 +45,42 @@ impl Expr for VoidExpr {
     fn divide(&self, _other: &Expression) -> Expression {
         VoidExpr::new()
     }
+
+    fn modulo(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn power(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_and(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_or(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn bit_xor(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_left(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn shift_right(&self, _other: &Expression) -> Expression {
+        VoidExpr::new()
+    }
+
+    fn negate(&self) -> Expression {
+        VoidExpr::new()
+    }
 }
 
 impl std::fmt::Display for VoidExpr {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", "void")
+        write!(f, "void")
     }
 }
@@ -0,0 +1,177 @@
+use super::ctx::Ctx;
+use super::error::Error;
+use super::exprs::*;
+use super::lex::*;
+use super::output::OutputBuffer;
+use super::parse::*;
+use super::value::Value;
+
+/// An embeddable phpxx interpreter.
+///
+/// Variables and functions stay defined between calls, so source can be fed in piece by piece.
+pub struct Interpreter {
+    ctx: Ctx,
+}
+
+impl Interpreter {
+    pub fn new() -> Interpreter {
+        Interpreter::with_filename("<embedded>")
+    }
+
+    pub fn with_filename(filename: &str) -> Interpreter {
+        Interpreter {
+            ctx: Ctx::new(filename, ""),
+        }
+    }
+
+    pub fn ctx(&mut self) -> &mut Ctx {
+        &mut self.ctx
+    }
+
+    pub fn set_argv(&mut self, argv: Vec<String>) {
+        self.ctx.set_argv(argv);
+    }
+
+    pub fn set_lenient(&mut self, lenient: bool) {
+        self.ctx.lenient = lenient;
+    }
+
+    /// Replaces where echo writes to, which is buffered stdout by default
+    pub fn set_output(&mut self, output: Box<dyn std::io::Write>) {
+        self.ctx.flush_output();
+        self.ctx.output = output;
+    }
+
+    /// Sends all further output into a buffer that can be read back
+    pub fn capture_output(&mut self) -> OutputBuffer {
+        let buffer = OutputBuffer::new();
+        self.set_output(Box::new(buffer.clone()));
+        buffer
+    }
+
+    /// The status passed to exit() or die(), once the last script run has called either
+    pub fn exit_code(&self) -> Option<i32> {
+        self.ctx.exit_code
+    }
+
+    /// Parses a whole program, collecting every syntax error instead of stopping at the first
+    pub fn parse(&mut self, source: &str) -> Result<Vec<Expression>, Vec<Error>> {
+        let offset = self.ctx.append_source(source);
+        let (tokens, spans) = tokenize(&self.ctx.contents[offset..], offset);
+
+        let result = parse(&mut self.ctx, &mut Tokens::new(&tokens[..], &spans[..]));
+
+        if result.is_err() {
+            self.ctx.abandon_parse();
+        }
+
+        result
+    }
+
+    /// Runs a parsed program, a top-level return ends it early with the returned value
+    pub fn run(&mut self, statements: Vec<Expression>) -> Result<Value, Error> {
+        self.start();
+        self.ctx.run(statements);
+        self.finish(VoidExpr::new())
+    }
+
+    /// Evaluates source, which is either a single expression whose value is returned
+    /// or a list of statements. Only the first syntax error is returned.
+    pub fn eval_str(&mut self, source: &str) -> Result<Value, Error> {
+        self.start();
+        let offset = self.ctx.append_source(source);
+        let (tokens, spans) = tokenize(&self.ctx.contents[offset..], offset);
+        let mut tokens = Tokens::new(&tokens[..], &spans[..]);
+
+        if let Ok(expr) = parse_bare_expr(&mut self.ctx, &mut tokens) {
+            let value = expr.evaluate(&mut self.ctx);
+            return self.finish(value);
+        }
+
+        tokens.backtrack(0);
+
+        match parse(&mut self.ctx, &mut tokens) {
+            Ok(statements) => self.run(statements),
+            Err(mut errors) => {
+                self.ctx.abandon_parse();
+                Err(errors.remove(0))
+            }
+        }
+    }
+
+    pub fn call_function(&mut self, name: &str, args: Vec<Value>) -> Result<Value, Error> {
+        self.start();
+        let args = args.into_iter().map(Value::into).collect();
+        let value = self.ctx.call(name, args);
+        self.finish(value)
+    }
+
+    /// Reads a global variable, the '$' prefix is optional
+    pub fn get_global(&self, name: &str) -> Value {
+        match self.ctx.scopes[0].variables.borrow().get(&variable_name(name)) {
+            Some(value) => Value::from(value),
+            None => Value::Void,
+        }
+    }
+
+    /// Sets a global variable, the '$' prefix is optional
+    pub fn set_global<V: Into<Value>>(&mut self, name: &str, value: V) {
+        self.ctx.scopes[0]
+            .variables
+            .borrow_mut()
+            .insert(variable_name(name), value.into().into());
+    }
+
+    /// Registers a host function that scripts can call like any other builtin,
+    /// replacing any builtin of the same name
+    pub fn register_function<F>(&mut self, name: &str, function: F)
+    where
+        F: Fn(&mut Ctx, Vec<Expression>) -> Expression + 'static,
+    {
+        self.ctx.register_builtin(name, function);
+    }
+
+    pub fn remove_function(&mut self, name: &str) -> bool {
+        self.ctx.remove_builtin(name)
+    }
+
+    pub fn render_error(&self, error: &Error) -> String {
+        error.render(&self.ctx.filename, &self.ctx.contents)
+    }
+
+    fn start(&mut self) {
+        // A script that called exit() or die() stops only itself, not whatever runs next
+        self.ctx.exit_code = None;
+        self.ctx.error = None;
+    }
+
+    fn finish(&mut self, value: Expression) -> Result<Value, Error> {
+        self.ctx.raise_uncaught();
+        self.ctx.flush_output();
+
+        let value = match self.ctx.signal.take() {
+            Some(Signal::Return(returned)) => returned,
+            _ => value,
+        };
+
+        if let Some(error) = self.ctx.error.take() {
+            return Err(error);
+        }
+
+        Ok(Value::from(&value))
+    }
+}
+
+impl Default for Interpreter {
+    fn default() -> Interpreter {
+        Interpreter::new()
+    }
+}
+
+fn variable_name(name: &str) -> String {
+    if name.starts_with('$') {
+        name.to_string()
+    } else {
+        format!("${}", name)
+    }
+}
@@ -1,6 +1,59 @@
-use logos::Logos;
+use logos::{Lexer, Logos};
 use std::ops::Range;
 
+pub fn tokenize(source: &str, offset: usize) -> (Vec<Token>, Vec<Range<usize>>) {
+    // Spans are shifted by 'offset' so they index into the full source
+    // that 'source' was taken from
+    let mut lexer = Token::lexer(source);
+    let mut tokens: Vec<Token> = vec![];
+    let mut spans: Vec<Range<usize>> = vec![];
+
+    while let Some(token) = lexer.next() {
+        let span = lexer.span();
+        tokens.push(token);
+        spans.push(span.start + offset..span.end + offset);
+    }
+
+    (tokens, spans)
+}
+
+pub fn open_blocks(source: &str) -> isize {
+    // How many braces, parentheses and brackets are still open at the end of 'source',
+    // with a string or heredoc that hasn't been closed yet counting as one more
+    let (tokens, spans) = tokenize(source, 0);
+
+    let depth = tokens.iter().fold(0, |depth, token| match token {
+        Token::Begin | Token::Open | Token::BracketOpen => depth + 1,
+        Token::End | Token::Close | Token::BracketClose => depth - 1,
+        _ => depth,
+    });
+
+    let unclosed = match (tokens.last(), spans.last()) {
+        (Some(Token::Error), Some(span)) => source[span.clone()].starts_with(['"', '\'']),
+        (Some(Token::Heredoc), Some(span)) => !is_heredoc_closed(&source[span.clone()]),
+        _ => false,
+    };
+
+    if unclosed {
+        depth + 1
+    } else {
+        depth
+    }
+}
+
+fn is_heredoc_closed(heredoc: &str) -> bool {
+    // The last line starts with the marker named after '<<<' when the heredoc is closed
+    let header_end = match heredoc.find('\n') {
+        Some(header_end) => header_end,
+        None => return false,
+    };
+
+    let marker = heredoc[3..header_end].trim().trim_matches(|c| c == '\'' || c == '"');
+    let last_line = &heredoc[heredoc.rfind('\n').unwrap_or(0) + 1..];
+
+    !marker.is_empty() && header_end < heredoc.len() - last_line.len() && last_line.trim_start().starts_with(marker)
+}
+
 pub struct Tokens<'a> {
     items: &'a [Token],
     spans: &'a [Range<usize>],
@@ -9,7 +62,7 @@ pub struct Tokens<'a> {
 
 impl<'a> Tokens<'a> {
     pub fn new(items: &'a [Token], spans: &'a [Range<usize>]) -> Tokens<'a> {
-        Tokens { items: items, spans: spans, next: 0 }
+        Tokens { items, spans, next: 0 }
     }
 
     pub fn peek(&self) -> Option<&Token> {
@@ -44,10 +97,9 @@ impl<'a> Tokens<'a> {
     }
 
     pub fn span_explicit(&self) -> Option<std::ops::Range<usize>> {
-        if self.next <= self.spans.len() {
-            Some(self.spans[self.next - 1].clone())
-        } else {
-            None
+        match self.next.checked_sub(1) {
+            Some(index) => self.spans.get(index).cloned(),
+            None => None,
         }
     }
 
@@ -60,6 +112,14 @@ impl<'a> Tokens<'a> {
         self.spans.get(index)
     }
 
+    pub fn peek_starts_line(&self, contents: &str) -> bool {
+        // Whether the next token is the first one on its line
+        match (self.next.checked_sub(1).and_then(|prev| self.spans.get(prev)), self.spans.get(self.next)) {
+            (Some(prev), Some(next)) => contents[prev.end..next.start].contains('\n'),
+            _ => true,
+        }
+    }
+
     pub fn has_next(&self) -> bool {
         self.next < self.items.len()
     }
@@ -71,9 +131,9 @@ impl<'a> Iterator for Tokens<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         if self.next < self.items.len() {
             self.next += 1;
-            return Some(&self.items[self.next - 1])
+            Some(&self.items[self.next - 1])
         } else {
-            return None
+            None
         }
     }
 }
@@ -92,16 +152,46 @@ pub enum Token {
     #[regex("else")]
     Else,
 
+    #[regex("elseif")]
+    ElseIf,
+
     #[regex("while")]
     While,
 
-    #[regex(r#""([^"\\]|\\t|\\u|\\n|\\")*""#)]
+    #[regex("return")]
+    Return,
+
+    #[regex("try")]
+    Try,
+
+    #[regex("catch")]
+    Catch,
+
+    #[regex("finally")]
+    Finally,
+
+    #[regex("break")]
+    Break,
+
+    #[regex("continue")]
+    Continue,
+
+    // Escapes are checked by the parser so that bad ones get a helpful error
+    #[regex(r#""([^"\\]|\\(.|\n))*""#)]
     String,
 
+    #[regex(r"'([^'\\]|\\(.|\n))*'")]
+    RawString,
+
+    #[token("<<<", lex_heredoc)]
+    Heredoc,
+
     #[regex(r"(//|#).*\n", logos::skip)]
     Comment,
 
-    #[regex(r"-?[0-9][0-9_]*(\.[0-9][0-9_]*)?")]
+    // Covers 1_000, 1.5e-9, 0xFF, 0b1010 and 0o17, along with any letters stuck
+    // to the end so that the parser can point out the bad digit
+    #[regex(r"[0-9][0-9_]*(\.[0-9][0-9_]*)?([eE][+-]?)?[0-9a-zA-Z_]*")]
     Number,
 
     #[token("+")]
@@ -116,12 +206,66 @@ pub enum Token {
     #[token("/")]
     Divide,
     
+    #[token("%")]
+    Modulo,
+
+    #[token("**")]
+    Power,
+
+    #[token("&")]
+    BitAnd,
+
+    #[token("|")]
+    BitOr,
+
+    #[token("^")]
+    BitXor,
+
+    #[token("<<")]
+    ShiftLeft,
+
+    #[token(">>")]
+    ShiftRight,
+
+    #[token("==")]
+    Equal,
+
+    #[token("!=")]
+    NotEqual,
+
+    #[token("<")]
+    Less,
+
+    #[token(">")]
+    Greater,
+
+    #[token("<=")]
+    LessEqual,
+
+    #[token(">=")]
+    GreaterEqual,
+
+    #[token("&&")]
+    And,
+
+    #[token("||")]
+    Or,
+
+    #[token("!")]
+    Not,
+
     #[token("(")]
     Open,
     
     #[token(")")]
     Close,
     
+    #[token("[")]
+    BracketOpen,
+
+    #[token("]")]
+    BracketClose,
+
     #[token("{")]
     Begin,
     
@@ -134,6 +278,24 @@ pub enum Token {
     #[token("=")]
     Assign,
 
+    #[token("+=")]
+    PlusAssign,
+
+    #[token("-=")]
+    MinusAssign,
+
+    #[token("*=")]
+    MultiplyAssign,
+
+    #[token("/=")]
+    DivideAssign,
+
+    #[token("++")]
+    Increment,
+
+    #[token("--")]
+    Decrement,
+
     #[token("..")]
     Spread,
 
@@ -153,3 +315,65 @@ pub enum Token {
     #[error]
     Error,
 }
+
+impl Token {
+    pub fn symbol(&self) -> &'static str {
+        // How operators are written, for showing them back in syntax trees
+        match self {
+            Token::Plus => "+",
+            Token::Minus => "-",
+            Token::Multiply => "*",
+            Token::Divide => "/",
+            Token::Modulo => "%",
+            Token::Power => "**",
+            Token::BitAnd => "&",
+            Token::BitOr => "|",
+            Token::BitXor => "^",
+            Token::ShiftLeft => "<<",
+            Token::ShiftRight => ">>",
+            Token::Equal => "==",
+            Token::NotEqual => "!=",
+            Token::Less => "<",
+            Token::Greater => ">",
+            Token::LessEqual => "<=",
+            Token::GreaterEqual => ">=",
+            Token::And => "&&",
+            Token::Or => "||",
+            Token::Not => "!",
+            Token::Assign => "=",
+            Token::PlusAssign => "+=",
+            Token::MinusAssign => "-=",
+            Token::MultiplyAssign => "*=",
+            Token::DivideAssign => "/=",
+            Token::Increment => "++",
+            Token::Decrement => "--",
+            _ => "<?>",
+        }
+    }
+}
+
+fn lex_heredoc(lexer: &mut Lexer<Token>) {
+    // Takes everything up to and including the closing marker, which is the name
+    // after '<<<' alone on a line, or the rest of the source if there isn't one.
+    // The parser reports anything that is wrong with it.
+    let remainder = lexer.remainder();
+    let header_end = remainder.find('\n').unwrap_or(remainder.len());
+    let marker = remainder[..header_end].trim().trim_matches(|c| c == '\'' || c == '"');
+    let mut line_end = header_end;
+
+    while !marker.is_empty() && line_end < remainder.len() {
+        let line_start = line_end + 1;
+        line_end = remainder[line_start..].find('\n').map_or(remainder.len(), |index| line_start + index);
+
+        // Anything after the marker, such as ';' or ')', is lexed as usual
+        let line = remainder[line_start..line_end].trim_start_matches([' ', '\t']);
+        let after = line.strip_prefix(marker).map(|after| after.chars().next());
+
+        if let Some(None) | Some(Some(' ' | '\t' | ';' | ')' | ',')) = after {
+            lexer.bump(line_end - line.len() + marker.len());
+            return;
+        }
+    }
+
+    lexer.bump(remainder.len());
+}
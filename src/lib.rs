@@ -0,0 +1,19 @@
+#![allow(clippy::new_ret_no_self)]
+
+pub mod cli;
+pub mod ctx;
+pub mod error;
+pub mod exprs;
+pub mod json;
+pub mod lex;
+pub mod output;
+pub mod parse;
+
+mod builtins;
+mod interpreter;
+mod value;
+
+pub use error::Error;
+pub use interpreter::Interpreter;
+pub use output::OutputBuffer;
+pub use value::{Function, Value};
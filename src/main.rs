@@ -1,63 +1,164 @@
-mod ctx;
-mod error;
-mod exprs;
-mod lex;
-mod parse;
+mod repl;
 
 use colored::Colorize;
-use ctx::*;
-use lex::*;
-use logos::Logos;
-use parse::*;
+use phpxx::cli::*;
+use phpxx::lex::tokenize;
+use phpxx::{json, Interpreter};
+
+// Exit statuses, scripts choose their own through exit() and die()
+const EXIT_FAILURE: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+const EXIT_SYNTAX: i32 = 3;
 
 fn main() {
-    let filename = "main.phpxx";
-    let contents = match std::fs::read_to_string(&filename) {
-        Ok(result) => result,
-        Err(_) => {
-            println!(
-                "{}{}{}",
-                "Failed to read file '".red(),
-                filename.red(),
-                "'".red()
-            );
-            return;
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message.red());
+            eprintln!("{}", USAGE);
+            std::process::exit(EXIT_USAGE);
         }
     };
 
-    let mut ctx = Ctx::new(&contents);
-    let mut lexer = Token::lexer(&contents);
-    let mut tokens: Vec<Token> = vec![];
-    let mut spans: Vec<std::ops::Range<usize>> = vec![];
+    let dumping = options.dump_tokens || options.dump_ast;
 
-    loop {
-        let value = match lexer.next() {
-            Some(value) => value,
-            None => break
-        };
+    let code = match &options.command {
+        Command::Run(input) | Command::Check(input) if dumping => dump(input, &options),
+        Command::Run(input) => run(input, &options),
+        Command::Check(input) => check(input),
+        Command::Repl => repl::repl(&options),
+        Command::Help => {
+            println!("{}", USAGE);
+            0
+        }
+    };
 
-        tokens.push(value);
-        spans.push(lexer.span());
-    }
+    std::process::exit(code);
+}
+
+fn run(input: &Input, options: &Options) -> i32 {
+    let contents = match read(input) {
+        Some(contents) => contents,
+        None => return EXIT_FAILURE,
+    };
 
-    let statements = parse(&mut ctx, &mut Tokens::new(&tokens[..], &spans[..]));
+    let mut interpreter = Interpreter::with_filename(input.name());
+    interpreter.set_argv(options.script_args.clone());
+    interpreter.set_lenient(options.lenient);
 
-    let statements = match statements {
+    let statements = match interpreter.parse(&contents) {
+        Ok(statements) => statements,
+        Err(errors) => {
+            for error in errors.iter() {
+                eprint!("{}", interpreter.render_error(error));
+            }
+            return EXIT_SYNTAX;
+        }
+    };
+
+    let result = interpreter.run(statements);
+
+    if let Some(code) = interpreter.exit_code() {
+        return code;
+    }
+
+    match result {
+        Ok(_) => 0,
         Err(error) => {
-            match error.location {
-                Some(location) => println!(
-                    "{}{}{}{}",
-                    error.message.bold().red(),
-                    " - '".bold().red(),
-                    &ctx.contents[location].bold().red(),
-                    "'".bold().red()
-                ),
-                None => println!("{}", error.message.bold().red()),
+            eprint!("{}", interpreter.render_error(&error));
+            EXIT_FAILURE
+        }
+    }
+}
+
+fn check(input: &Input) -> i32 {
+    // Only parses the script, reporting every syntax error found
+    let contents = match read(input) {
+        Some(contents) => contents,
+        None => return EXIT_FAILURE,
+    };
+
+    let mut interpreter = Interpreter::with_filename(input.name());
+
+    match interpreter.parse(&contents) {
+        Ok(_) => 0,
+        Err(errors) => {
+            for error in errors.iter() {
+                eprint!("{}", interpreter.render_error(error));
             }
-            return;
+            EXIT_SYNTAX
         }
-        Ok(statements) => statements,
+    }
+}
+
+fn dump(input: &Input, options: &Options) -> i32 {
+    // Shows how the script is lexed and parsed, without running it
+    let contents = match read(input) {
+        Some(contents) => contents,
+        None => return EXIT_FAILURE,
     };
 
-    ctx.run(&statements);
+    if options.dump_tokens {
+        let (tokens, spans) = tokenize(&contents, 0);
+
+        if options.json {
+            let tokens: Vec<String> = tokens
+                .iter()
+                .zip(spans.iter())
+                .map(|(token, span)| {
+                    json::object(&[
+                        ("token", json::string(&format!("{:?}", token))),
+                        ("span", json::span(span)),
+                        ("text", json::string(&contents[span.clone()])),
+                    ])
+                })
+                .collect();
+            println!("{}", json::array(&tokens));
+        } else {
+            for (token, span) in tokens.iter().zip(spans.iter()) {
+                let token = format!("{:?}", token);
+                println!("{:>4}..{:<4} {:<10} {:?}", span.start, span.end, token, &contents[span.clone()]);
+            }
+        }
+    }
+
+    if options.dump_ast {
+        let mut interpreter = Interpreter::with_filename(input.name());
+
+        let statements = match interpreter.parse(&contents) {
+            Ok(statements) => statements,
+            Err(errors) => {
+                for error in errors.iter() {
+                    eprint!("{}", interpreter.render_error(error));
+                }
+                return EXIT_SYNTAX;
+            }
+        };
+
+        if options.json {
+            let statements: Vec<String> = statements.iter().map(|x| x.jsonify()).collect();
+            println!("{}", json::array(&statements));
+        } else {
+            for statement in statements.iter() {
+                println!("{}", statement.visualize());
+            }
+        }
+    }
+
+    0
+}
+
+fn read(input: &Input) -> Option<String> {
+    match input.read() {
+        Ok(contents) => Some(contents),
+        Err(_) => {
+            println!(
+                "{}{}{}",
+                "Failed to read file '".red(),
+                input.name().red(),
+                "'".red()
+            );
+            None
+        }
+    }
 }
@@ -0,0 +1,40 @@
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+
+/// An in-memory output sink, clones share the same buffer so output
+/// written through the interpreter can be read back afterwards
+#[derive(Clone, Default)]
+pub struct OutputBuffer {
+    buffer: Rc<RefCell<Vec<u8>>>,
+}
+
+impl OutputBuffer {
+    pub fn new() -> OutputBuffer {
+        OutputBuffer::default()
+    }
+
+    pub fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.buffer.borrow()).into_owned()
+    }
+
+    pub fn take(&self) -> String {
+        let bytes = std::mem::take(&mut *self.buffer.borrow_mut());
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+}
+
+impl Write for OutputBuffer {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.buffer.borrow_mut().extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+pub fn stdout() -> Box<dyn Write> {
+    Box::new(std::io::BufWriter::new(std::io::stdout()))
+}
@@ -3,36 +3,115 @@ use super::error::Error;
 use super::exprs::*;
 use super::lex::*;
 
-pub fn parse(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Vec<Expression>, Error> {
+mod numbers;
+mod strings;
+
+use numbers::*;
+use strings::*;
+
+pub fn parse(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Vec<Expression>, Vec<Error>> {
     let mut statements: Vec<Expression> = Vec::new();
 
-    loop {
-        let token = match tokens.next() {
-            Some(token) => token,
-            None => break,
-        };
+    while let Some(token) = tokens.next() {
+        let start = tokens.remember();
 
-        let statement = parse_statement(ctx, tokens, &token, &mut statements)?;
-        statements.push(statement);
+        match parse_statement(ctx, tokens, token) {
+            Ok(statement) => statements.push(statement),
+            Err(error) => {
+                synchronize(ctx, tokens, start, &error);
+                ctx.parse_errors.push(error);
+            }
+        }
     }
 
-    Ok(statements)
+    if ctx.parse_errors.is_empty() {
+        Ok(statements)
+    } else {
+        Err(std::mem::take(&mut ctx.parse_errors))
+    }
+}
+
+fn synchronize(ctx: &Ctx, tokens: &mut Tokens, start: usize, error: &Error) {
+    // Skips tokens after a syntax error until one that looks like the start of a new statement,
+    // blocks opened along the way are skipped as a whole so their '}' isn't mistaken for ours
+    let mut depth: usize = 0;
+
+    // A '{' or '}' that the statement took and then failed on still belongs to the block
+    // structure, so it is put back. The token that started the statement is never put back,
+    // so every error moves past at least one token.
+    let failed_at = tokens.remember().checked_sub(1).filter(|&index| index >= start);
+
+    if let Some(index) = failed_at {
+        let is_boundary = matches!(tokens.get_token(index), Some(Token::Begin) | Some(Token::End));
+
+        if is_boundary && tokens.get_span(index) == error.location.as_ref() {
+            tokens.backtrack(index);
+        }
+    }
+
+    while let Some(token) = tokens.peek() {
+        match token {
+            Token::Begin => depth += 1,
+            Token::End if depth > 0 => depth -= 1,
+            Token::End | Token::Echo | Token::Function | Token::If | Token::While if depth == 0 => return,
+            Token::Return | Token::Try | Token::Break | Token::Continue if depth == 0 => return,
+            Token::Variable | Token::Identifier if depth == 0 && tokens.peek_starts_line(&ctx.contents) => return,
+            _ => (),
+        }
+
+        tokens.advance();
+    }
+}
+
+pub fn parse_bare_expr(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression, Error> {
+    // Parses input that consists of exactly one expression and nothing else
+    let expr = parse_expr(ctx, tokens);
+
+    // Errors inside blocks, such as the body of a closure, are collected rather than returned
+    let mut errors = std::mem::take(&mut ctx.parse_errors);
+    let expr = expr?;
+
+    if !errors.is_empty() {
+        return Err(errors.remove(0));
+    }
+
+    if tokens.has_next() {
+        tokens.next();
+        return make_simple_parse_error("Expected end of expression", tokens);
+    }
+
+    Ok(expr)
 }
 
 fn parse_statement(
     ctx: &mut Ctx,
     tokens: &mut Tokens,
     lead_token: &Token,
-    statements: &mut Vec<Expression>,
 ) -> Result<Expression, Error> {
     match lead_token {
         Token::Echo => parse_echo(ctx, tokens),
+        Token::Variable if tokens.peek() == Some(&Token::Open) => {
+            // '$f(...)' calls a function value rather than assigning
+            tokens.backtrack(tokens.remember() - 1);
+            parse_expr(ctx, tokens)
+        }
         Token::Variable => parse_assign(ctx, tokens),
+        Token::Increment | Token::Decrement if tokens.peek() == Some(&Token::Variable) => {
+            parse_increment(ctx, tokens)
+        }
         Token::Identifier => parse_call_expr(ctx, tokens),
-        Token::Function => parse_function(ctx, tokens, statements.len()),
-        Token::End => parse_end(ctx, tokens, statements),
+        Token::Function => parse_function(ctx, tokens),
+        Token::End => Err(Error::new(
+            "Unexpected '}'".to_string(),
+            Some(tokens.span()),
+        )
+        .with_label("no block to close")),
         Token::If => parse_conditional(ctx, tokens, false),
         Token::While => parse_conditional(ctx, tokens, true),
+        Token::Return => parse_return(ctx, tokens),
+        Token::Try => parse_try(ctx, tokens),
+        Token::Break => parse_loop_control(ctx, tokens, false),
+        Token::Continue => parse_loop_control(ctx, tokens, true),
         _ => Err(Error::new(
             "Unknown Statement".to_string(),
             Some(tokens.span()),
@@ -43,16 +122,22 @@ fn parse_statement(
 fn parse_echo(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression, Error> {
     let mut newline = true;
 
-    if tokens.has_next() && *tokens.peek().unwrap() == Token::Minus {
-        // Skip over '-' that indicates option to echo
-        tokens.next();
+    // A '-' followed by a name is an option to echo, anything else after it
+    // (such as a number or a call like '-count($a)') is a value to negate and echo.
+    // A call needs the '(' right after the name, so 'echo -n (1 + 2)' still has the option
+    let next = tokens.remember();
+    let is_call = tokens.get_token(next + 2) == Some(&Token::Open)
+        && match (tokens.get_span(next + 1), tokens.get_span(next + 2)) {
+            (Some(name), Some(open)) => name.end == open.start,
+            _ => false,
+        };
+    let is_option = tokens.get_token(next) == Some(&Token::Minus)
+        && tokens.get_token(next + 1) == Some(&Token::Identifier)
+        && !is_call;
 
-        if !tokens.has_next() || *tokens.next().unwrap() != Token::Identifier {
-            return Err(Error::new(
-                "Expected option after '-' after 'echo'".to_string(),
-                Some(tokens.span()),
-            ));
-        }
+    if is_option {
+        // Skip over '-' that indicates option to echo
+        tokens.advance_by(2);
 
         match &ctx.contents[tokens.span()] {
             "n" => {
@@ -75,33 +160,108 @@ fn parse_echo(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression, Error> {
 }
 
 fn parse_assign(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression, Error> {
-    let variable = &ctx.contents[tokens.span()];
-
-    if match tokens.next() {
-        Some(Token::Assign) => false,
-        _ => true,
-    } {
-        return Err(Error::new(
-            "Expected '=' after variable name in statement".to_string(),
-            Some(tokens.span()),
-        ));
-    }
+    let variable = ctx.contents[tokens.span()].to_string();
+    let variable_span = tokens.span();
+
+    let operator = match tokens.next() {
+        Some(Token::Assign) => None,
+        Some(Token::PlusAssign) => Some(MathOperator::Plus),
+        Some(Token::MinusAssign) => Some(MathOperator::Minus),
+        Some(Token::MultiplyAssign) => Some(MathOperator::Multiply),
+        Some(Token::DivideAssign) => Some(MathOperator::Divide),
+        Some(Token::Increment) => return Ok(AssignExpr::increment(variable, MathOperator::Plus, false, tokens.span())),
+        Some(Token::Decrement) => return Ok(AssignExpr::increment(variable, MathOperator::Minus, false, tokens.span())),
+        Some(Token::BracketOpen) => return parse_index_assign(ctx, tokens, variable, variable_span),
+        _ => {
+            return Err(Error::new(
+                "Expected '=' after variable name in statement".to_string(),
+                Some(tokens.span()),
+            )
+            .with_label("or one of '+=', '-=', '*=', '/=', '++' and '--'"))
+        }
+    };
 
+    let span = tokens.span();
     let value = parse_expr(ctx, tokens)?;
-    Ok(AssignExpr::new(variable.to_string(), value))
+
+    Ok(match operator {
+        Some(operator) => AssignExpr::compound(variable, operator, value, span),
+        None => AssignExpr::new(variable, value, span),
+    })
 }
 
-fn parse_function(ctx: &mut Ctx, tokens: &mut Tokens, address: usize) -> Result<Expression, Error> {
-    if ctx.parsing_function.is_some() {
-        return Err(Error::new(
-            "Already in function".to_string(),
-            Some(tokens.span()),
-        ));
+fn parse_index_assign(ctx: &mut Ctx, tokens: &mut Tokens, variable: String, variable_span: Span) -> Result<Expression, Error> {
+    // '$a[1] = 2', '$a[0][1] = 2' and '$a[] = 2', where every index but the last is read
+    let mut target = VariableExpr::new(variable);
+
+    loop {
+        let open_span = tokens.span();
+
+        let index = if tokens.peek() == Some(&Token::BracketClose) {
+            tokens.next();
+            None
+        } else {
+            match parse_index(ctx, tokens, open_span.clone())? {
+                Index::At(index) => Some(index),
+                Index::Slice(..) => {
+                    return Err(Error::new("Cannot assign to a slice".to_string(), Some(open_span.start..tokens.span().end)))
+                }
+            }
+        };
+
+        let span = variable_span.start..tokens.span().end;
+
+        match (index, tokens.next()) {
+            (Some(index), Some(Token::BracketOpen)) => target = IndexExpr::new(target, Index::At(index), span),
+            (index, Some(Token::Assign)) => {
+                let value = parse_expr(ctx, tokens)?;
+                return Ok(IndexAssignExpr::new(target, index, value, span));
+            }
+            _ => {
+                return Err(Error::new(
+                    "Expected '=' after index in statement".to_string(),
+                    Some(tokens.span()),
+                ))
+            }
+        }
+    }
+}
+
+fn parse_increment(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression, Error> {
+    // '++$a' and '--$a', as a statement or inside an expression
+    let operator_span = tokens.span();
+    let operator = match tokens.get_token(tokens.remember() - 1) {
+        Some(Token::Increment) => MathOperator::Plus,
+        _ => MathOperator::Minus,
+    };
+
+    if tokens.peek() != Some(&Token::Variable) {
+        // Anything else after '--' is negated twice, so '--5' is still 5
+        if operator == MathOperator::Minus {
+            let value = parse_primary_expr(ctx, tokens)?;
+            return Ok(UnaryExpr::new(UnaryOperator::Negate, UnaryExpr::new(UnaryOperator::Negate, value, operator_span.clone()), operator_span));
+        }
+
+        tokens.next();
+        return make_simple_parse_error("Expected a variable after '++'", tokens);
     }
 
-    let name = match tokens.next() {
-        Some(Token::Identifier) => ctx.contents[tokens.span()].to_string(),
+    tokens.next();
+    let variable = ctx.contents[tokens.span()].to_string();
+    Ok(AssignExpr::increment(variable, operator, true, operator_span))
+}
+
+fn parse_function(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression, Error> {
+    // 'function name($a) { ... }' declares a function, while 'function ($a) { ... }'
+    // without a name is an expression that makes a closure
+    let name = match tokens.peek() {
+        Some(Token::Identifier) => {
+            tokens.next();
+            Some(ctx.contents[tokens.span()].to_string())
+        }
+        Some(Token::Open) => None,
         _ => {
+            tokens.next();
             return Err(Error::new(
                 "Expected name of function".to_string(),
                 Some(tokens.span()),
@@ -159,28 +319,13 @@ fn parse_function(ctx: &mut Ctx, tokens: &mut Tokens, address: usize) -> Result<
         }
     }
 
-    ctx.add_function(name, address + 1, args);
-    ctx.parsing_function = Some(address);
+    // Loops around a function don't count for 'break' and 'continue' inside of it
+    let loop_depth = std::mem::replace(&mut ctx.loop_depth, 0);
+    let body = parse_block(ctx, tokens, "function");
+    ctx.loop_depth = loop_depth;
 
-    // Will be overwritten later with jump instruction
-    Ok(VoidExpr::new())
-}
-
-fn parse_end(
-    ctx: &mut Ctx,
-    tokens: &mut Tokens,
-    statements: &mut Vec<Expression>,
-) -> Result<Expression, Error> {
-    if ctx.parsing_function.is_none() {
-        return Err(Error::new(
-            "Unexpected '}'".to_string(),
-            Some(tokens.span()),
-        ));
-    }
-
-    statements[ctx.parsing_function.unwrap()] = JumpExpr::new(statements.len() + 1);
-    ctx.parsing_function = None;
-    Ok(CallExpr::new("throw".to_string(), vec![]))
+    let body = body?;
+    Ok(FunctionDeclExpr::new(name, args, body))
 }
 
 fn parse_conditional(
@@ -205,17 +350,27 @@ fn parse_conditional(
         }
     }
 
-    let when_true = parse_block(ctx, tokens, conditional_kind_name)?;
+    ctx.loop_depth += is_while as usize;
+    let when_true = parse_block(ctx, tokens, conditional_kind_name);
+    ctx.loop_depth -= is_while as usize;
+
+    let when_true = when_true?;
 
+    // 'elseif' and 'else if' chain another 'if' statement on as the only thing in the 'else' block
     let when_false = match tokens.peek() {
+        Some(Token::ElseIf) => {
+            tokens.next();
+            vec![parse_conditional(ctx, tokens, false)?]
+        }
         Some(Token::Else) => {
             tokens.next();
             match tokens.next() {
                 Some(Token::Begin) => parse_block(ctx, tokens, conditional_kind_name)?,
+                Some(Token::If) => vec![parse_conditional(ctx, tokens, false)?],
                 _ => {
                     return Err(Error::new(
                         format!(
-                            "Expected '{{' after 'else' keyword of '{}' statement",
+                            "Expected '{{' or 'if' after 'else' keyword of '{}' statement",
                             conditional_kind_name
                         ),
                         Some(tokens.span()),
@@ -231,12 +386,114 @@ fn parse_conditional(
     ))
 }
 
+fn parse_return(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression, Error> {
+    // A 'return' with nothing after it on the same line returns void
+    let value = match tokens.peek() {
+        None | Some(Token::End) => VoidExpr::new(),
+        Some(_) if tokens.peek_starts_line(&ctx.contents) => VoidExpr::new(),
+        Some(_) => parse_expr(ctx, tokens)?,
+    };
+
+    Ok(ReturnExpr::new(value))
+}
+
+fn parse_loop_control(ctx: &mut Ctx, tokens: &mut Tokens, is_continue: bool) -> Result<Expression, Error> {
+    // 'break' and 'continue' leave the innermost loop, or the loop that many levels out with 'break 2'
+    let keyword = if is_continue { "continue" } else { "break" };
+    let keyword_span = tokens.span();
+
+    let depth = match tokens.peek() {
+        Some(Token::Number) if !tokens.peek_starts_line(&ctx.contents) => {
+            tokens.next();
+
+            match ctx.contents[tokens.span()].parse::<usize>() {
+                Ok(depth) if depth > 0 => depth,
+                _ => {
+                    return Err(Error::new(
+                        format!("Expected a positive whole number of loops after '{}'", keyword),
+                        Some(tokens.span()),
+                    ))
+                }
+            }
+        }
+        _ => 1,
+    };
+
+    if ctx.loop_depth == 0 {
+        return Err(Error::new(format!("Cannot use '{}' outside of a loop", keyword), Some(keyword_span)));
+    }
+
+    if depth > ctx.loop_depth {
+        return Err(Error::new(
+            format!("Cannot '{}' {} loops from inside {}", keyword, depth, ctx.loop_depth),
+            Some(keyword_span.start..tokens.span().end),
+        ));
+    }
+
+    Ok(LoopControlExpr::new(depth, is_continue))
+}
+
+fn parse_try(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression, Error> {
+    let try_span = tokens.span();
+
+    match tokens.next() {
+        Some(Token::Begin) => (),
+        _ => return make_simple_parse_error("Expected '{' after 'try'", tokens),
+    }
+
+    let body = parse_block(ctx, tokens, "try")?;
+
+    let catch = match tokens.peek() {
+        Some(Token::Catch) => {
+            tokens.next();
+
+            let variable = match (tokens.next(), tokens.next()) {
+                (Some(Token::Open), Some(Token::Variable)) => ctx.contents[tokens.span()].to_string(),
+                _ => return make_simple_parse_error("Expected '($name)' after 'catch'", tokens),
+            };
+
+            match (tokens.next(), tokens.next()) {
+                (Some(Token::Close), Some(Token::Begin)) => (),
+                _ => return make_simple_parse_error("Expected ') {' after variable of 'catch'", tokens),
+            }
+
+            Some((variable, parse_block(ctx, tokens, "catch")?))
+        }
+        _ => None,
+    };
+
+    let finally = match tokens.peek() {
+        Some(Token::Finally) => {
+            tokens.next();
+
+            match tokens.next() {
+                Some(Token::Begin) => (),
+                _ => return make_simple_parse_error("Expected '{' after 'finally'", tokens),
+            }
+
+            Some(parse_block(ctx, tokens, "finally")?)
+        }
+        _ => None,
+    };
+
+    if catch.is_none() && finally.is_none() {
+        return Err(Error::new(
+            "Expected 'catch' or 'finally' after 'try' block".to_string(),
+            Some(try_span),
+        )
+        .with_label("'try' needs a 'catch' or 'finally' block"));
+    }
+
+    Ok(TryExpr::new(body, catch, finally))
+}
+
 fn parse_block(
     ctx: &mut Ctx,
     tokens: &mut Tokens,
     statement_kind_name: &str,
 ) -> Result<Vec<Expression>, Error> {
     let mut statements: Vec<Expression> = Vec::new();
+    let begin_span = tokens.span();
 
     loop {
         let lead_token = match tokens.next() {
@@ -247,14 +504,25 @@ fn parse_block(
                         statement_kind_name
                     ),
                     Some(tokens.span()),
+                )
+                .with_secondary(
+                    begin_span,
+                    &format!("'{}' block opened here", statement_kind_name),
                 ))
             }
             Some(Token::End) => break,
             token => token.unwrap(),
         };
 
-        let stmt = parse_statement(ctx, tokens, &lead_token, &mut statements)?;
-        statements.push(stmt);
+        let start = tokens.remember();
+
+        match parse_statement(ctx, tokens, lead_token) {
+            Ok(stmt) => statements.push(stmt),
+            Err(error) => {
+                synchronize(ctx, tokens, start, &error);
+                ctx.parse_errors.push(error);
+            }
+        }
     }
 
     Ok(statements)
@@ -266,6 +534,59 @@ fn parse_expr(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression, Error> {
 }
 
 fn parse_primary_expr(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression, Error> {
+    let start = tokens.get_span(tokens.remember()).map_or(0, |span| span.start);
+    let mut value = parse_atom_expr(ctx, tokens)?;
+
+    // Indexing and calls bind tighter than anything else, so '-$a[0]' is '-($a[0])'
+    loop {
+        match tokens.peek() {
+            Some(Token::BracketOpen) => {
+                tokens.next();
+                let index = parse_index(ctx, tokens, tokens.span())?;
+                value = IndexExpr::new(value, index, start..tokens.span().end);
+            }
+            Some(Token::Open) if !tokens.peek_starts_line(&ctx.contents) => {
+                tokens.next();
+                let args = parse_call_args(ctx, tokens)?;
+                value = InvokeExpr::new(value, args, start..tokens.span().end);
+            }
+            _ => break,
+        }
+    }
+
+    Ok(value)
+}
+
+fn parse_index(ctx: &mut Ctx, tokens: &mut Tokens, open_span: Span) -> Result<Index, Error> {
+    // Everything between '[' and ']', either '$i' or a slice such as '1..3', '..3' or '1..'
+    let start = match tokens.peek() {
+        Some(Token::Spread) => None,
+        _ => Some(parse_expr(ctx, tokens)?),
+    };
+
+    let index = match (start, tokens.peek()) {
+        (start, Some(Token::Spread)) => {
+            tokens.next();
+
+            let end = match tokens.peek() {
+                Some(Token::BracketClose) => None,
+                _ => Some(parse_expr(ctx, tokens)?),
+            };
+
+            Index::Slice(start, end)
+        }
+        (Some(start), _) => Index::At(start),
+        (None, _) => unreachable!(),
+    };
+
+    match tokens.next() {
+        Some(Token::BracketClose) => Ok(index),
+        _ => Err(Error::new("Expected ']' to close index".to_string(), Some(tokens.span()))
+            .with_secondary(open_span, "index opened here")),
+    }
+}
+
+fn parse_atom_expr(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression, Error> {
     let token = tokens.next();
 
     let token = match token {
@@ -274,21 +595,34 @@ fn parse_primary_expr(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression,
     };
 
     match token {
-        Token::String => {
-            let value = snailquote::unescape(&ctx.contents[tokens.span()])
-                .or_else(|_err| make_simple_parse_error::<String>("bad string escape", tokens))?;
-
-            Ok(StringExpr::new(value))
-        }
-        Token::Number => {
-            let value = ctx.contents[tokens.span()]
-                .parse::<f64>()
-                .or_else(|_err| make_simple_parse_error::<f64>("bad number", tokens))?;
-
-            Ok(NumberExpr::new(value))
+        Token::String => parse_string(ctx, tokens),
+        Token::RawString => parse_raw_string(ctx, tokens),
+        Token::Heredoc => parse_heredoc(ctx, tokens),
+        Token::Number => parse_number(ctx, tokens),
+        Token::Minus => parse_unary_expr(ctx, tokens, UnaryOperator::Negate),
+        Token::Not => parse_unary_expr(ctx, tokens, UnaryOperator::Not),
+        Token::Plus => parse_unary_expr(ctx, tokens, UnaryOperator::Identity),
+        Token::Variable => {
+            let variable = ctx.contents[tokens.span()].to_string();
+
+            // '$a++' and '$a--', unless the '++' or '--' starts the next line
+            let operator = match tokens.peek() {
+                Some(Token::Increment) => Some(MathOperator::Plus),
+                Some(Token::Decrement) => Some(MathOperator::Minus),
+                _ => None,
+            };
+
+            match operator {
+                Some(operator) if !tokens.peek_starts_line(&ctx.contents) => {
+                    tokens.next();
+                    Ok(AssignExpr::increment(variable, operator, false, tokens.span()))
+                }
+                _ => Ok(VariableExpr::new(variable)),
+            }
         }
-        Token::Variable => Ok(VariableExpr::new(ctx.contents[tokens.span()].to_string())),
+        Token::Increment | Token::Decrement => parse_increment(ctx, tokens),
         Token::Identifier => parse_call_expr(ctx, tokens),
+        Token::Function if tokens.peek() == Some(&Token::Open) => parse_function(ctx, tokens),
         Token::Spread => {
             let inner = parse_primary_expr(ctx, tokens)?;
             Ok(SpreadExpr::new(inner))
@@ -305,6 +639,23 @@ fn parse_primary_expr(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression,
     }
 }
 
+fn parse_unary_expr(ctx: &mut Ctx, tokens: &mut Tokens, operator: UnaryOperator) -> Result<Expression, Error> {
+    // Prefix operators apply to the value right after them, so '-$a + 1' is '(-$a) + 1'
+    let span = tokens.span();
+    let value = parse_primary_expr(ctx, tokens)?;
+
+    // Numbers are lexed without a sign, so signed literals are folded here
+    if let Some(number) = value.as_any().downcast_ref::<NumberExpr>() {
+        match operator {
+            UnaryOperator::Negate => return Ok(NumberExpr::new(-number.value)),
+            UnaryOperator::Identity => return Ok(NumberExpr::new(number.value)),
+            UnaryOperator::Not => {}
+        }
+    }
+
+    Ok(UnaryExpr::new(operator, value, span))
+}
+
 fn parse_op_expr(
     ctx: &mut Ctx,
     tokens: &mut Tokens,
@@ -330,10 +681,18 @@ fn parse_op_expr(
             return Ok(primary);
         }
 
+        if let Some(comparison) = Comparison::from_token(operator) {
+            primary = parse_compare_expr(ctx, tokens, primary, comparison, operator_precedence)?;
+            continue;
+        }
+
+        if let Some(math) = MathOperator::from_token(operator) {
+            primary = parse_math_expr(ctx, tokens, primary, math, operator_precedence)?;
+            continue;
+        }
+
         let next_primary = match *operator {
-            Token::Plus | Token::Minus | Token::Multiply | Token::Divide => {
-                parse_math_expr(ctx, tokens, primary, operator_precedence)?
-            }
+            Token::And | Token::Or => parse_logical_expr(ctx, tokens, primary, operator_precedence)?,
             _ => return Ok(primary),
         };
 
@@ -342,14 +701,41 @@ fn parse_op_expr(
 }
 
 fn parse_math_expr(
+    ctx: &mut Ctx,
+    tokens: &mut Tokens,
+    left: Expression,
+    operator: MathOperator,
+    operator_precedence: usize,
+) -> Result<Expression, Error> {
+    tokens.next();
+    let operator_span = tokens.span();
+    let right = parse_rhs_expr(ctx, tokens, operator_precedence)?;
+    Ok(MathExpr::new(left, operator, right, operator_span))
+}
+
+fn parse_compare_expr(
+    ctx: &mut Ctx,
+    tokens: &mut Tokens,
+    left: Expression,
+    operator: Comparison,
+    operator_precedence: usize,
+) -> Result<Expression, Error> {
+    tokens.next();
+    let operator_span = tokens.span();
+    let right = parse_rhs_expr(ctx, tokens, operator_precedence)?;
+    Ok(CompareExpr::new(left, operator, right, operator_span))
+}
+
+fn parse_logical_expr(
     ctx: &mut Ctx,
     tokens: &mut Tokens,
     left: Expression,
     operator_precedence: usize,
 ) -> Result<Expression, Error> {
     let operator = tokens.next().unwrap();
+    let operator_span = tokens.span();
     let right = parse_rhs_expr(ctx, tokens, operator_precedence)?;
-    Ok(MathExpr::new(left, operator, right))
+    Ok(LogicalExpr::new(left, operator, right, operator_span))
 }
 
 fn parse_rhs_expr(
@@ -379,7 +765,7 @@ fn parse_rhs_expr(
 
 fn parse_call_expr(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression, Error> {
     let function = ctx.contents[tokens.span()].to_string();
-    let mut args = vec![];
+    let function_span = tokens.span();
 
     if match tokens.next() {
         Some(token) => *token != Token::Open,
@@ -391,6 +777,15 @@ fn parse_call_expr(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression, Err
         ));
     }
 
+    let args = parse_call_args(ctx, tokens)?;
+    Ok(CallExpr::new(function, args, function_span))
+}
+
+fn parse_call_args(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Vec<Expression>, Error> {
+    // Arguments up to and including the ')', after the '(' has been taken
+    let open_span = tokens.span();
+    let mut args = vec![];
+
     loop {
         if match tokens.peek() {
             Some(Token::Close) => true,
@@ -398,7 +793,8 @@ fn parse_call_expr(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression, Err
                 return Err(Error::new(
                     "Expected ')' before end of file".to_string(),
                     Some(tokens.span()),
-                ))
+                )
+                .with_secondary(open_span, "call opened here"))
             }
             _ => false,
         } {
@@ -422,19 +818,39 @@ fn parse_call_expr(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression, Err
         }
     }
 
-    Ok(CallExpr::new(function, args))
+    Ok(args)
 }
 
 fn get_op_precedence(token: &Token) -> Option<usize> {
     // Higher precedence = Higher Priority
+    //
+    // The usual order is reversed all the way through, so '||' binds tightest
+    // and '**' binds loosest. 'a == 1 || 2' is 'a == (1 || 2)', and
+    // '1 + 2 < 3' is '1 + (2 < 3)'. Operators of the same precedence group
+    // left to right, including '**'.
 
-    return match token {
-        Token::Plus => Some(6),
-        Token::Minus => Some(6),
-        Token::Multiply => Some(5),
-        Token::Divide => Some(5),
+    match token {
+        Token::Or => Some(11),
+        Token::And => Some(10),
+        Token::BitOr => Some(9),
+        Token::BitXor => Some(8),
+        Token::BitAnd => Some(7),
+        Token::Equal => Some(6),
+        Token::NotEqual => Some(6),
+        Token::Less => Some(5),
+        Token::Greater => Some(5),
+        Token::LessEqual => Some(5),
+        Token::GreaterEqual => Some(5),
+        Token::ShiftLeft => Some(4),
+        Token::ShiftRight => Some(4),
+        Token::Plus => Some(3),
+        Token::Minus => Some(3),
+        Token::Multiply => Some(2),
+        Token::Divide => Some(2),
+        Token::Modulo => Some(2),
+        Token::Power => Some(1),
         _ => None,
-    };
+    }
 }
 
 fn make_simple_parse_error<T>(message: &str, tokens: &mut Tokens) -> Result<T, Error> {
@@ -0,0 +1,134 @@
+use super::*;
+
+// Numbers are lexed loosely, including any letters stuck to them, so that
+// a bad digit gets pointed at here instead of becoming a confusing token
+
+pub fn parse_number(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression, Error> {
+    let span = tokens.span();
+    let text = &ctx.contents[span.clone()];
+
+    let value = match text.get(..2) {
+        Some("0x") | Some("0X") => parse_radix(text, span.start, Radix::Hex)?,
+        Some("0b") | Some("0B") => parse_radix(text, span.start, Radix::Binary)?,
+        Some("0o") | Some("0O") => parse_radix(text, span.start, Radix::Octal)?,
+        _ => parse_decimal(text, span.start)?,
+    };
+
+    Ok(NumberExpr::new(value))
+}
+
+#[derive(Clone, Copy)]
+enum Radix {
+    Binary,
+    Octal,
+    Decimal,
+    Hex,
+}
+
+impl Radix {
+    fn base(self) -> u32 {
+        match self {
+            Radix::Binary => 2,
+            Radix::Octal => 8,
+            Radix::Decimal => 10,
+            Radix::Hex => 16,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Radix::Binary => "binary",
+            Radix::Octal => "octal",
+            Radix::Decimal => "decimal",
+            Radix::Hex => "hex",
+        }
+    }
+
+    fn allowed(self) -> &'static str {
+        match self {
+            Radix::Binary => "0 and 1",
+            Radix::Octal => "0 to 7",
+            Radix::Decimal => "0 to 9",
+            Radix::Hex => "0 to 9 and a to f",
+        }
+    }
+}
+
+fn parse_radix(text: &str, offset: usize, radix: Radix) -> Result<f64, Error> {
+    let digits = digits(&text[2..], offset + 2, radix)?;
+
+    if digits.is_empty() {
+        return Err(Error::new(format!("Expected {} digits after '{}'", radix.name(), &text[..2]), Some(offset..offset + 2)));
+    }
+
+    // Accumulating as a float lets literals too large for an integer still work
+    Ok(digits
+        .chars()
+        .fold(0.0, |value, digit| value * radix.base() as f64 + digit.to_digit(radix.base()).unwrap() as f64))
+}
+
+fn parse_decimal(text: &str, offset: usize) -> Result<f64, Error> {
+    // Splits the number into its integer, fraction and exponent parts
+    let exponent_start = text.find(['e', 'E']).unwrap_or(text.len());
+    let fraction_start = text[..exponent_start].find('.').unwrap_or(exponent_start);
+
+    let mut number = digits(&text[..fraction_start], offset, Radix::Decimal)?;
+
+    if fraction_start < exponent_start {
+        number.push('.');
+        number.push_str(&digits(&text[fraction_start + 1..exponent_start], offset + fraction_start + 1, Radix::Decimal)?);
+    }
+
+    if exponent_start < text.len() {
+        let mut exponent = &text[exponent_start + 1..];
+        let mut exponent_offset = offset + exponent_start + 1;
+        number.push('e');
+
+        if let Some(sign) = exponent.strip_prefix(['+', '-']) {
+            number.push_str(&exponent[..1]);
+            exponent = sign;
+            exponent_offset += 1;
+        }
+
+        let exponent = digits(exponent, exponent_offset, Radix::Decimal)?;
+
+        if exponent.is_empty() {
+            return Err(Error::new("Expected digits in exponent".to_string(), Some(offset + exponent_start..offset + text.len()))
+                .with_label("such as 1e-9"));
+        }
+
+        number.push_str(&exponent);
+    }
+
+    Ok(number.parse::<f64>().unwrap())
+}
+
+fn digits(text: &str, offset: usize, radix: Radix) -> Result<String, Error> {
+    // Checks a run of digits, returning it without any '_' separators
+    let mut result = String::new();
+    let bytes = text.as_bytes();
+
+    for (index, c) in text.char_indices() {
+        if c == '_' {
+            let between_digits = index > 0
+                && index + 1 < bytes.len()
+                && bytes[index - 1] != b'_'
+                && bytes[index + 1] != b'_';
+
+            if !between_digits {
+                return Err(Error::new("Misplaced '_' digit separator".to_string(), Some(offset + index..offset + index + 1))
+                    .with_label("separators can only go between digits"));
+            }
+        } else if c.is_digit(radix.base()) {
+            result.push(c);
+        } else {
+            return Err(Error::new(
+                format!("Invalid digit '{}' in {} number", c, radix.name()),
+                Some(offset + index..offset + index + c.len_utf8()),
+            )
+            .with_label(&format!("{} numbers only use {}", radix.name(), radix.allowed())));
+        }
+    }
+
+    Ok(result)
+}
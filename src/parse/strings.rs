@@ -0,0 +1,302 @@
+use super::*;
+
+// Double-quoted strings and heredocs interpolate "$var", "{$var}" and "{expr}",
+// single-quoted strings and nowdocs are taken as they are written
+
+const ESCAPES: &str = "\\n \\t \\r \\v \\f \\e \\0 \\\\ \\\" \\' \\$ \\{ \\xHH \\u{HHHH}";
+
+pub fn parse_string(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression, Error> {
+    let span = tokens.span();
+    let mut builder = StringBuilder::new();
+
+    scan_interpolated(ctx, span.start + 1, span.end - 1, &mut builder)?;
+    Ok(builder.finish())
+}
+
+pub fn parse_raw_string(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression, Error> {
+    // Like PHP, only "\'" and "\\" mean anything in a single-quoted string
+    let span = tokens.span();
+    let mut value = String::new();
+    let mut chars = ctx.contents[span.start + 1..span.end - 1].chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some('\'')) | ('\\', Some('\\')) => value.extend(chars.next()),
+            _ => value.push(c),
+        }
+    }
+
+    Ok(StringExpr::new(value))
+}
+
+pub fn parse_heredoc(ctx: &mut Ctx, tokens: &mut Tokens) -> Result<Expression, Error> {
+    // <<<EOT or <<<"EOT" starts a heredoc, which interpolates like a double-quoted string,
+    // and <<<'EOT' starts a nowdoc, which doesn't. The closing marker may be indented,
+    // in which case that indentation is removed from every line of the text.
+    let span = tokens.span();
+    let text = &ctx.contents[span.clone()];
+
+    let header_length = text.find('\n').unwrap_or(text.len());
+    let header = text[3..header_length].trim();
+    let header_span = span.start..span.start + header_length;
+
+    let (marker, is_nowdoc) = if header.len() > 2 && header.starts_with('\'') && header.ends_with('\'') {
+        (&header[1..header.len() - 1], true)
+    } else if header.len() > 2 && header.starts_with('"') && header.ends_with('"') {
+        (&header[1..header.len() - 1], false)
+    } else {
+        (header, false)
+    };
+
+    if variable_name_length(marker) != marker.len() || marker.is_empty() {
+        return Err(Error::new("Expected a name after '<<<'".to_string(), Some(header_span))
+            .with_label("such as <<<EOT"));
+    }
+
+    let closing_start = text.rfind('\n').map_or(text.len(), |index| index + 1);
+    let closing = &text[closing_start..];
+    let indent = &closing[..closing.len() - closing.trim_start_matches([' ', '\t']).len()];
+
+    if header_length == text.len() || &closing[indent.len()..] != marker {
+        return Err(Error::new(format!("Unterminated heredoc, expected '{}' to close it", marker), Some(header_span))
+            .with_label("heredoc starts here"));
+    }
+
+    let marker = marker.to_string();
+    let indent = indent.to_string();
+    let mut builder = StringBuilder::new();
+
+    // The newlines after the header and before the closing marker aren't part of the text
+    let body_start = span.start + header_length + 1;
+    let body_end = span.start + closing_start - 1;
+    let mut lines = vec![];
+
+    if body_end >= body_start {
+        let mut line_start = body_start;
+
+        for line in ctx.contents[body_start..body_end].split('\n') {
+            lines.push(line_start..line_start + line.len());
+            line_start += line.len() + 1;
+        }
+    }
+
+    for (number, line) in lines.iter().enumerate() {
+        if number > 0 {
+            builder.literal.push('\n');
+        }
+
+        let text = &ctx.contents[line.clone()];
+
+        if !text.starts_with(&indent) {
+            if text.trim().is_empty() {
+                continue;
+            }
+
+            return Err(Error::new(
+                format!("Line in heredoc is indented less than its closing '{}'", marker),
+                Some(line.clone()),
+            )
+            .with_secondary(span.end - marker.len()..span.end, "closing marker is here"));
+        }
+
+        let content_start = line.start + indent.len();
+
+        if is_nowdoc {
+            builder.literal.push_str(&ctx.contents[content_start..line.end]);
+        } else {
+            scan_interpolated(ctx, content_start, line.end, &mut builder)?;
+        }
+    }
+
+    Ok(builder.finish())
+}
+
+struct StringBuilder {
+    parts: Vec<Expression>,
+    literal: String,
+}
+
+impl StringBuilder {
+    fn new() -> StringBuilder {
+        StringBuilder { parts: vec![], literal: String::new() }
+    }
+
+    fn push(&mut self, expr: Expression) {
+        if !self.literal.is_empty() {
+            let literal = std::mem::take(&mut self.literal);
+            self.parts.push(StringExpr::new(literal));
+        }
+
+        self.parts.push(expr);
+    }
+
+    fn finish(mut self) -> Expression {
+        // Only strings that interpolate something need to be joined at runtime
+        if self.parts.is_empty() {
+            return StringExpr::new(self.literal);
+        }
+
+        if !self.literal.is_empty() {
+            self.parts.push(StringExpr::new(self.literal));
+        }
+
+        InterpolateExpr::new(self.parts)
+    }
+}
+
+fn scan_interpolated(ctx: &mut Ctx, start: usize, end: usize, builder: &mut StringBuilder) -> Result<(), Error> {
+    let mut index = start;
+
+    while index < end {
+        let c = ctx.contents[index..end].chars().next().unwrap();
+
+        if c == '\\' {
+            let (value, length) = parse_escape(&ctx.contents[index..end], index)?;
+            builder.literal.push(value);
+            index += length;
+            continue;
+        }
+
+        let name_length = if c == '$' {
+            variable_name_length(&ctx.contents[index + 1..end])
+        } else {
+            0
+        };
+
+        if name_length > 0 {
+            let name = ctx.contents[index..index + 1 + name_length].to_string();
+            builder.push(VariableExpr::new(name));
+            index += 1 + name_length;
+        } else if c == '{' {
+            let (expr, close_end) = parse_interpolation(ctx, index, end)?;
+            builder.push(expr);
+            index = close_end;
+        } else {
+            builder.literal.push(c);
+            index += c.len_utf8();
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_escape(text: &str, offset: usize) -> Result<(char, usize), Error> {
+    // Decodes the escape sequence at the start of 'text', which begins with a backslash,
+    // returning the character along with how many bytes the sequence took up
+    let escape = match text[1..].chars().next() {
+        Some(escape) => escape,
+        None => {
+            return Err(Error::new("Expected an escape sequence after '\\'".to_string(), Some(offset..offset + 1))
+                .with_label("use '\\\\' for a literal backslash"))
+        }
+    };
+
+    let simple = match escape {
+        'n' => Some('\n'),
+        't' => Some('\t'),
+        'r' => Some('\r'),
+        'v' => Some('\x0B'),
+        'f' => Some('\x0C'),
+        'e' => Some('\x1B'),
+        '0' => Some('\0'),
+        '\\' | '"' | '\'' | '$' | '{' => Some(escape),
+        _ => None,
+    };
+
+    if let Some(value) = simple {
+        return Ok((value, 2));
+    }
+
+    match escape {
+        'x' => {
+            let digits = text.get(2..4).filter(|digits| digits.chars().all(|c| c.is_ascii_hexdigit()));
+
+            let value = match digits {
+                Some(digits) => u8::from_str_radix(digits, 16).unwrap(),
+                None => {
+                    return Err(Error::new("Expected two hex digits after '\\x'".to_string(), Some(offset..offset + 2))
+                        .with_label("such as \\x41"))
+                }
+            };
+
+            if value > 0x7F {
+                return Err(Error::new(format!("'\\x{:02X}' is not an ASCII character", value), Some(offset..offset + 4))
+                    .with_label(&format!("use \\u{{{:X}}} for this character", value)));
+            }
+
+            Ok((value as char, 4))
+        }
+        'u' => {
+            if !text[2..].starts_with('{') {
+                return Err(Error::new("Expected '{' after '\\u'".to_string(), Some(offset..offset + 2))
+                    .with_label("such as \\u{1F600}"));
+            }
+
+            let close = match text[3..].find('}') {
+                Some(close) => 3 + close,
+                None => {
+                    return Err(Error::new("Unterminated unicode escape".to_string(), Some(offset..offset + text.len()))
+                        .with_label("expected '}'"))
+                }
+            };
+
+            let digits = &text[3..close];
+            let span = offset..offset + close + 1;
+
+            if digits.is_empty() || digits.len() > 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(Error::new("Expected one to six hex digits in unicode escape".to_string(), Some(span)));
+            }
+
+            match char::from_u32(u32::from_str_radix(digits, 16).unwrap()) {
+                Some(value) => Ok((value, close + 1)),
+                None => Err(Error::new(format!("'\\u{{{}}}' is not a valid unicode character", digits), Some(span))),
+            }
+        }
+        _ => Err(Error::new(
+            format!("Unknown escape sequence '\\{}'", escape),
+            Some(offset..offset + 1 + escape.len_utf8()),
+        )
+        .with_label(&format!("valid escapes are {}", ESCAPES))),
+    }
+}
+
+fn variable_name_length(text: &str) -> usize {
+    // Length of the variable name at the start of 'text', not counting the '$'
+    let mut chars = text.char_indices();
+
+    match chars.next() {
+        Some((_, c)) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return 0,
+    }
+
+    chars
+        .find(|(_, c)| !(c.is_ascii_alphanumeric() || *c == '_'))
+        .map_or(text.len(), |(index, _)| index)
+}
+
+fn parse_interpolation(ctx: &mut Ctx, open: usize, end: usize) -> Result<(Expression, usize), Error> {
+    // Parses the expression in "{...}" that starts at 'open' within a string,
+    // returning it along with where the string continues after the '}'
+    let (items, spans) = tokenize(&ctx.contents[open + 1..end], open + 1);
+    let mut tokens = Tokens::new(&items[..], &spans[..]);
+
+    if !tokens.has_next() {
+        return Err(Error::new("Expected expression inside '{}'".to_string(), Some(open..end))
+            .with_label("use '\\{' for a literal '{'"));
+    }
+
+    let expr = parse_expr(ctx, &mut tokens)?;
+
+    match tokens.next() {
+        Some(Token::End) => Ok((expr, tokens.span().end)),
+        next => {
+            let span = match next {
+                Some(_) => tokens.span(),
+                None => end..end,
+            };
+
+            Err(Error::new("Expected '}' to close interpolation".to_string(), Some(span))
+                .with_secondary(open..open + 1, "interpolation opened here"))
+        }
+    }
+}
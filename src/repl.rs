@@ -0,0 +1,90 @@
+use phpxx::cli::Options;
+use phpxx::lex::open_blocks;
+use phpxx::Interpreter;
+use std::io::{BufRead, Write};
+
+const PROMPT: &str = "phpxx> ";
+const CONTINUE_PROMPT: &str = "   ... ";
+
+pub fn repl(options: &Options) -> i32 {
+    let mut interpreter = Interpreter::with_filename("<repl>");
+    let mut history: Vec<String> = vec![];
+
+    interpreter.set_argv(options.script_args.clone());
+    interpreter.set_lenient(options.lenient);
+
+    while let Some(input) = read_input() {
+        match input.trim() {
+            "" => continue,
+            ":quit" | ":exit" => break,
+            ":history" => {
+                for (index, entry) in history.iter().enumerate() {
+                    println!("{:>4}  {}", index + 1, entry);
+                }
+                continue;
+            }
+            _ => (),
+        }
+
+        // '!n' re-runs the n-th entry from history
+        let input = match input.trim().strip_prefix('!') {
+            Some(index) => match index
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| history.get(index.wrapping_sub(1)))
+            {
+                Some(entry) => format!("{}\n", entry),
+                None => {
+                    println!("No such history entry '{}'", index);
+                    continue;
+                }
+            },
+            None => input,
+        };
+
+        history.push(input.trim_end().to_string());
+        // Bare expressions have their value echoed back
+        match interpreter.eval_str(&input) {
+            Ok(value) if !value.is_void() => println!("{}", value.visualize()),
+            Ok(_) => (),
+            Err(error) => eprint!("{}", interpreter.render_error(&error)),
+        }
+
+        if let Some(code) = interpreter.exit_code() {
+            return code;
+        }
+    }
+
+    0
+}
+
+fn read_input() -> Option<String> {
+    // Reads a single line, or keeps reading lines while a block, bracket or string is still open
+    let stdin = std::io::stdin();
+    let mut input = String::new();
+
+    loop {
+        print!(
+            "{}",
+            if input.is_empty() {
+                PROMPT
+            } else {
+                CONTINUE_PROMPT
+            }
+        );
+        std::io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+        match stdin.lock().read_line(&mut line) {
+            Ok(0) | Err(_) => {
+                println!();
+                return if input.is_empty() { None } else { Some(input) };
+            }
+            _ => input.push_str(&line),
+        }
+
+        if open_blocks(&input) <= 0 {
+            return Some(input);
+        }
+    }
+}
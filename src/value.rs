@@ -0,0 +1,135 @@
+use super::exprs::*;
+use match_cast::match_cast;
+
+/// A plain Rust copy of a phpxx value, used to move data in and out of the interpreter.
+///
+/// Arrays are copied during conversion, so a converted array no longer aliases
+/// the array that the script sees. Functions are kept by reference instead.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Void,
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Function(Function),
+}
+
+/// A closure taken out of the interpreter, which can be handed back to it and still be called
+#[derive(Clone)]
+pub struct Function(FunctionExpr);
+
+impl PartialEq for Function {
+    fn eq(&self, other: &Function) -> bool {
+        // Same as '==' in scripts, a function is only equal to itself
+        self.0.uid() == other.0.uid()
+    }
+}
+
+impl std::fmt::Debug for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_tuple("Function").field(&self.0.visualize()).finish()
+    }
+}
+
+impl Value {
+    pub fn from_expression(expression: &Expression) -> Value {
+        let any = expression.as_any();
+
+        match_cast!(any {
+            val as NumberExpr => {
+                Value::Number(val.value)
+            },
+            val as StringExpr => {
+                Value::String(val.value.clone())
+            },
+            val as ArrayExpr => {
+                Value::Array(val.value.borrow().iter().map(Value::from_expression).collect())
+            },
+            val as FunctionExpr => {
+                Value::Function(Function(val.clone()))
+            },
+        })
+        .unwrap_or(Value::Void)
+    }
+
+    pub fn to_expression(&self) -> Expression {
+        match self {
+            Value::Void => VoidExpr::new(),
+            Value::Number(value) => NumberExpr::new(*value),
+            Value::String(value) => StringExpr::new(value.clone()),
+            Value::Array(items) => {
+                ArrayExpr::new(items.iter().map(Value::to_expression).collect(), false)
+            }
+            Value::Function(function) => Box::new(function.0.clone()),
+        }
+    }
+
+    pub fn is_void(&self) -> bool {
+        *self == Value::Void
+    }
+
+    pub fn visualize(&self) -> String {
+        self.to_expression().visualize()
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.to_expression().stringify())
+    }
+}
+
+impl From<&Expression> for Value {
+    fn from(expression: &Expression) -> Value {
+        Value::from_expression(expression)
+    }
+}
+
+impl From<Value> for Expression {
+    fn from(value: Value) -> Expression {
+        value.to_expression()
+    }
+}
+
+impl From<()> for Value {
+    fn from(_: ()) -> Value {
+        Value::Void
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Value {
+        Value::Number(value)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(value: i32) -> Value {
+        Value::Number(value as f64)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Value {
+        // Booleans follow the same convention as eq() and lt()
+        Value::Number(if value { 1.0 } else { 0.0 })
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Value {
+        Value::String(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Value {
+        Value::String(value)
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(items: Vec<T>) -> Value {
+        Value::Array(items.into_iter().map(Into::into).collect())
+    }
+}
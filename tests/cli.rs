@@ -0,0 +1,72 @@
+// Tests for parsing command-line arguments
+
+use phpxx::cli::{parse_args, Command, Input, Options};
+
+fn parse(args: &[&str]) -> Result<Options, String> {
+    parse_args(args.iter().map(|arg| arg.to_string()))
+}
+
+#[test]
+fn run_takes_a_script_and_its_arguments() {
+    let options = parse(&["run", "main.phpxx", "--", "first", "--lenient", "-e"]).unwrap();
+
+    assert!(matches!(&options.command, Command::Run(Input::File(path)) if path == "main.phpxx"));
+    assert_eq!(options.script_args, ["first", "--lenient", "-e"]);
+    assert!(!options.lenient);
+}
+
+#[test]
+fn dash_reads_the_script_from_stdin() {
+    let options = parse(&["run", "-"]).unwrap();
+    assert!(matches!(options.command, Command::Run(Input::Stdin)));
+
+    let options = parse(&["check", "-"]).unwrap();
+    assert!(matches!(options.command, Command::Check(Input::Stdin)));
+}
+
+#[test]
+fn inline_code_is_given_with_e() {
+    let options = parse(&["-e", "echo 1", "--", "a"]).unwrap();
+    assert!(matches!(&options.command, Command::Run(Input::Inline(code)) if code == "echo 1"));
+    assert_eq!(options.script_args, ["a"]);
+
+    let options = parse(&["run", "-e", "echo 2"]).unwrap();
+    assert!(matches!(&options.command, Command::Run(Input::Inline(code)) if code == "echo 2"));
+
+    assert_eq!(parse(&["-e"]).err().unwrap(), "Expected code after '-e'");
+}
+
+#[test]
+fn flags_may_come_anywhere_before_the_script_arguments() {
+    let options = parse(&["--lenient", "run", "main.phpxx", "--dump-ast", "--json"]).unwrap();
+
+    assert!(options.lenient);
+    assert!(options.dump_ast);
+    assert!(options.json);
+    assert!(!options.dump_tokens);
+}
+
+#[test]
+fn misplaced_arguments_are_rejected() {
+    assert_eq!(
+        parse(&["run", "main.phpxx", "first"]).err().unwrap(),
+        "Unexpected argument 'first', script arguments go after '--'"
+    );
+    assert_eq!(parse(&["run"]).err().unwrap(), "Expected script path");
+    assert_eq!(parse(&["start"]).err().unwrap(), "Unknown command 'start'");
+    assert_eq!(parse(&[]).err().unwrap(), "No command given");
+    assert_eq!(parse(&["--", "first"]).err().unwrap(), "No command given");
+}
+
+#[test]
+fn json_needs_a_dump() {
+    assert!(parse(&["run", "main.phpxx", "--json"]).is_err());
+    assert!(parse(&["run", "main.phpxx", "--json", "--dump-tokens"]).is_ok());
+}
+
+#[test]
+fn other_commands_take_no_script() {
+    assert!(matches!(parse(&["repl", "--", "a"]).unwrap().command, Command::Repl));
+    assert!(matches!(parse(&["--help"]).unwrap().command, Command::Help));
+    assert!(parse(&["repl", "main.phpxx"]).is_err());
+}
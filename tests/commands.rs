@@ -0,0 +1,72 @@
+// Runs the phpxx binary and checks what it prints and the status it exits with
+
+use std::process::{Command, Output};
+
+fn phpxx(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_phpxx")).args(args).output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn exit_status_follows_the_outcome() {
+    assert_eq!(phpxx(&["-e", "echo 1"]).status.code(), Some(0));
+    assert_eq!(phpxx(&["-e", "echo nope()"]).status.code(), Some(1));
+    assert_eq!(phpxx(&["run"]).status.code(), Some(2));
+    assert_eq!(phpxx(&["-e", "$a = "]).status.code(), Some(3));
+}
+
+#[test]
+fn scripts_choose_their_own_exit_status() {
+    let output = phpxx(&["-e", "echo 1\nexit(7)\necho 2"]);
+    assert_eq!(output.status.code(), Some(7));
+    assert_eq!(stdout(&output), "1\n");
+
+    let output = phpxx(&["-e", "die(\"bye\")\necho 2"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "bye\n");
+}
+
+#[test]
+fn dump_tokens_shows_each_token_with_its_span() {
+    let output = phpxx(&["-e", "echo 1", "--dump-tokens"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "   0..4    Echo       \"echo\"\n   5..6    Number     \"1\"\n");
+
+    let output = phpxx(&["-e", "echo 1", "--dump-tokens", "--json"]);
+    assert_eq!(
+        stdout(&output),
+        "[{\"token\":\"Echo\",\"span\":[0,4],\"text\":\"echo\"},{\"token\":\"Number\",\"span\":[5,6],\"text\":\"1\"}]\n"
+    );
+}
+
+#[test]
+fn dump_ast_shows_the_parsed_script_without_running_it() {
+    let output = phpxx(&["-e", "echo 1 - 2 / 3\nexit(5)", "--dump-ast"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "echo ((1 - 2) / 3)\nexit(5)\n");
+
+    let output = phpxx(&["-e", "echo 1", "--dump-ast", "--json"]);
+    assert_eq!(
+        stdout(&output),
+        "[{\"type\":\"Echo\",\"newline\":true,\"value\":{\"type\":\"Number\",\"value\":1}}]\n"
+    );
+
+    assert_eq!(phpxx(&["-e", "echo (", "--dump-ast"]).status.code(), Some(3));
+}
+
+#[test]
+fn json_without_a_dump_is_rejected() {
+    let output = phpxx(&["-e", "echo 1", "--json"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(stdout(&output), "");
+}
+
+#[test]
+fn lenient_scripts_keep_running_after_runtime_errors() {
+    let output = phpxx(&["-e", "echo nope()\necho 2", "--lenient"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "void\n2\n");
+}
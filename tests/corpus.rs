@@ -0,0 +1,68 @@
+// Runs every script in tests/corpus and compares what it prints with the
+// '.out' file next to it. Runtime and syntax errors are appended to the output
+// as "error: <message>" lines so that failing cases can be covered too.
+// Every script is run with "first" and "second" as its arguments.
+//
+// Set PHPXX_BLESS=1 to write the current output as the expected output.
+
+use phpxx::Interpreter;
+use std::path::Path;
+
+fn run_script(source: &str) -> String {
+    let mut interpreter = Interpreter::with_filename("<corpus>");
+    let output = interpreter.capture_output();
+    interpreter.set_argv(vec!["first".to_string(), "second".to_string()]);
+
+    match interpreter.parse(source) {
+        Ok(statements) => {
+            if let Err(error) = interpreter.run(statements) {
+                return format!("{}error: {}\n", output.take(), error);
+            }
+        }
+        Err(errors) => {
+            return errors.iter().map(|error| format!("error: {}\n", error)).collect();
+        }
+    }
+
+    output.take()
+}
+
+#[test]
+fn corpus() {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus");
+    let bless = std::env::var_os("PHPXX_BLESS").is_some();
+
+    let mut scripts: Vec<_> = std::fs::read_dir(&directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "phpxx"))
+        .collect();
+    scripts.sort();
+
+    let mut failures = vec![];
+
+    for script in scripts.iter() {
+        let source = std::fs::read_to_string(script).unwrap();
+        let actual = run_script(&source);
+        let expected_path = script.with_extension("out");
+
+        if bless {
+            std::fs::write(&expected_path, &actual).unwrap();
+            continue;
+        }
+
+        let expected = std::fs::read_to_string(&expected_path).unwrap_or_default();
+
+        if actual != expected {
+            failures.push(format!(
+                "{}\n--- expected\n{}--- actual\n{}",
+                script.display(),
+                expected,
+                actual
+            ));
+        }
+    }
+
+    assert!(!scripts.is_empty(), "no scripts found in {}", directory.display());
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
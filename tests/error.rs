@@ -0,0 +1,123 @@
+// Tests for how diagnostics are rendered, with colors turned off so the exact text can be compared
+
+use phpxx::Error;
+
+fn render(error: &Error, contents: &str) -> String {
+    colored::control::set_override(false);
+    error.render("main.phpxx", contents)
+}
+
+#[test]
+fn error_without_location_is_only_the_message() {
+    let error = Error::new("Something went wrong".to_string(), None);
+    assert_eq!(render(&error, "echo 1"), "error: Something went wrong\n");
+}
+
+#[test]
+fn header_points_at_line_and_column() {
+    let contents = "echo 1\necho nope()\n";
+    let error = Error::new("Undefined function 'nope'".to_string(), Some(12..16))
+        .with_label("not defined anywhere");
+
+    assert_eq!(
+        render(&error, contents),
+        "\
+error: Undefined function 'nope'
+ --> main.phpxx:2:6
+  |
+2 | echo nope()
+  |      ^^^^ not defined anywhere
+"
+    );
+}
+
+#[test]
+fn empty_span_gets_a_single_marker() {
+    let contents = "$a = ";
+    let error = Error::new("Expected an expression".to_string(), Some(5..5));
+
+    assert_eq!(
+        render(&error, contents),
+        "\
+error: Expected an expression
+ --> main.phpxx:1:6
+  |
+1 | $a = 
+  |      ^
+"
+    );
+}
+
+#[test]
+fn span_across_lines_is_underlined_to_the_end_of_its_first_line() {
+    let contents = "echo \"ab\ncd\"";
+    let error = Error::new("Bad string".to_string(), Some(5..12));
+
+    assert_eq!(
+        render(&error, contents),
+        "\
+error: Bad string
+ --> main.phpxx:1:6
+  |
+1 | echo \"ab
+  |      ^^^
+"
+    );
+}
+
+#[test]
+fn tabs_are_kept_so_markers_line_up() {
+    let contents = "if 1 {\n\techo nope()\n}";
+    let error = Error::new("Undefined function 'nope'".to_string(), Some(13..17));
+
+    assert_eq!(
+        render(&error, contents),
+        "\
+error: Undefined function 'nope'
+ --> main.phpxx:2:7
+  |
+2 | \techo nope()
+  | \t     ^^^^
+"
+    );
+}
+
+#[test]
+fn columns_count_characters_not_bytes() {
+    let contents = "echo \"é\" + nope()";
+    let error = Error::new("Undefined function 'nope'".to_string(), Some(12..16));
+
+    assert_eq!(
+        render(&error, contents),
+        "\
+error: Undefined function 'nope'
+ --> main.phpxx:1:12
+  |
+1 | echo \"é\" + nope()
+  |            ^^^^
+"
+    );
+}
+
+#[test]
+fn secondary_labels_are_shown_in_source_order() {
+    let contents = "function f($a {\n    echo $a\n}\n\n\n\n\n\n\n\nf(1)";
+    let error = Error::new("Expected ')'".to_string(), Some(14..15))
+        .with_label("expected ')' here")
+        .with_secondary(10..11, "to close this '('")
+        .with_secondary(37..38, "called here");
+
+    assert_eq!(
+        render(&error, contents),
+        "\
+error: Expected ')'
+  --> main.phpxx:1:15
+   |
+ 1 | function f($a {
+   |           - to close this '('
+   |               ^ expected ')' here
+11 | f(1)
+   | - called here
+"
+    );
+}
@@ -0,0 +1,237 @@
+// Tests for embedding the interpreter through its public API
+
+use phpxx::{Interpreter, Value};
+
+#[test]
+fn top_level_return_is_the_result() {
+    let mut interpreter = Interpreter::new();
+    let output = interpreter.capture_output();
+
+    let statements = interpreter.parse("echo 1\nreturn 7\necho 2").unwrap();
+    assert_eq!(interpreter.run(statements).ok(), Some(Value::Number(7.0)));
+    assert_eq!(output.take(), "1\n");
+
+    assert_eq!(interpreter.eval_str("$a = 3\nreturn $a + 1").ok(), Some(Value::Number(4.0)));
+}
+
+#[test]
+fn syntax_error_inside_closure_is_reported() {
+    let mut interpreter = Interpreter::new();
+
+    assert!(interpreter.eval_str("function ($x) { $x = }").is_err());
+    assert_eq!(interpreter.eval_str("1 + 1").ok(), Some(Value::Number(2.0)));
+}
+
+#[test]
+fn closures_keep_their_identity_through_globals() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("$double = function ($x) { return $x * 2 }").unwrap();
+
+    let double = interpreter.get_global("double");
+    assert!(matches!(double, Value::Function(_)));
+    assert_eq!(double, interpreter.get_global("$double"));
+
+    interpreter.set_global("copy", double);
+    assert_eq!(interpreter.eval_str("$copy == $double").ok(), Some(Value::Number(1.0)));
+    assert_eq!(interpreter.eval_str("$copy(21)").ok(), Some(Value::Number(42.0)));
+}
+
+#[test]
+fn run_stops_at_a_runtime_error() {
+    let mut interpreter = Interpreter::new();
+    let output = interpreter.capture_output();
+
+    let statements = interpreter.parse("echo 1\necho arr(1, 2)[5]\necho 3").unwrap();
+    assert!(interpreter.run(statements).is_err());
+    assert_eq!(output.take(), "1\n");
+
+    // The interpreter can still be used afterwards
+    assert_eq!(interpreter.eval_str("echo 4").ok(), Some(Value::Void));
+    assert_eq!(output.take(), "4\n");
+}
+
+#[test]
+fn parse_collects_every_syntax_error() {
+    let mut interpreter = Interpreter::new();
+
+    let errors = interpreter.parse("$a = \necho 1\n$b = \n").unwrap_err();
+    assert_eq!(errors.len(), 2);
+}
+
+#[test]
+fn eval_str_takes_expressions_and_statements() {
+    let mut interpreter = Interpreter::new();
+
+    assert_eq!(interpreter.eval_str("1 + 2 * 3").ok(), Some(Value::Number(9.0)));
+    assert_eq!(interpreter.eval_str("\"a\" + \"b\"").ok(), Some(Value::from("ab")));
+    assert_eq!(interpreter.eval_str("$a = 1\n$a++").ok(), Some(Value::Void));
+    assert_eq!(interpreter.eval_str("$a").ok(), Some(Value::Number(2.0)));
+    assert!(interpreter.eval_str("$a = ").is_err());
+}
+
+#[test]
+fn globals_are_shared_with_scripts() {
+    let mut interpreter = Interpreter::new();
+
+    assert!(interpreter.get_global("missing").is_void());
+
+    interpreter.set_global("name", "world");
+    interpreter.set_global("$items", vec![1, 2, 3]);
+    assert_eq!(interpreter.eval_str("\"hello \" + $name").ok(), Some(Value::from("hello world")));
+    assert_eq!(interpreter.eval_str("count($items)").ok(), Some(Value::Number(3.0)));
+
+    interpreter.eval_str("$items[] = 4\n$total = 10").unwrap();
+    assert_eq!(interpreter.get_global("$items"), Value::from(vec![1, 2, 3, 4]));
+    assert_eq!(interpreter.get_global("total"), Value::Number(10.0));
+}
+
+#[test]
+fn registered_functions_can_be_called_and_removed() {
+    let mut interpreter = Interpreter::new();
+
+    interpreter.register_function("twice", |_ctx, args| {
+        let value = args.first().map(Value::from).unwrap_or(Value::Void);
+        match value {
+            Value::Number(number) => Value::Number(number * 2.0).into(),
+            other => other.into(),
+        }
+    });
+    assert_eq!(interpreter.eval_str("twice(21)").ok(), Some(Value::Number(42.0)));
+    assert_eq!(interpreter.call_function("twice", vec![Value::from(4)]).ok(), Some(Value::Number(8.0)));
+
+    // Builtins of the same name are replaced
+    interpreter.register_function("count", |_ctx, _args| Value::from(-1).into());
+    assert_eq!(interpreter.eval_str("count(arr(1, 2))").ok(), Some(Value::Number(-1.0)));
+
+    assert!(interpreter.remove_function("twice"));
+    assert!(!interpreter.remove_function("twice"));
+    assert!(interpreter.eval_str("twice(21)").is_err());
+}
+
+#[test]
+fn call_function_runs_script_functions() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str("function add($a, $b) { return $a + $b }").unwrap();
+
+    let sum = interpreter.call_function("add", vec![Value::from(1), Value::from(2)]);
+    assert_eq!(sum.ok(), Some(Value::Number(3.0)));
+    assert!(interpreter.call_function("missing", vec![]).is_err());
+}
+
+#[test]
+fn captured_output_can_be_read_back() {
+    let mut interpreter = Interpreter::new();
+    let output = interpreter.capture_output();
+
+    interpreter.eval_str("echo \"a\"\necho 1 + 1").unwrap();
+    assert_eq!(output.contents(), "a\n2\n");
+    assert_eq!(output.take(), "a\n2\n");
+    assert_eq!(output.contents(), "");
+
+    interpreter.set_output(Box::new(std::io::sink()));
+    interpreter.eval_str("echo \"b\"").unwrap();
+    assert_eq!(output.contents(), "");
+}
+
+#[test]
+fn exit_stops_the_script_with_its_status() {
+    let mut interpreter = Interpreter::new();
+    let output = interpreter.capture_output();
+
+    assert_eq!(interpreter.eval_str("echo 1\nexit(4)\necho 2").ok(), Some(Value::Void));
+    assert_eq!(interpreter.exit_code(), Some(4));
+    assert_eq!(output.take(), "1\n");
+
+    // Unwinds out of loops and functions, skipping everything after the call
+    let source = "
+function leave($code) {
+    while 1 {
+        exit($code)
+        echo \"in loop\"
+    }
+    echo \"in function\"
+}
+leave(2)
+echo \"after\"";
+    assert!(interpreter.eval_str(source).is_ok());
+    assert_eq!(interpreter.exit_code(), Some(2));
+    assert_eq!(output.take(), "");
+
+    assert!(interpreter.eval_str("exit()").is_ok());
+    assert_eq!(interpreter.exit_code(), Some(0));
+}
+
+#[test]
+fn die_writes_its_message_to_the_output() {
+    let mut interpreter = Interpreter::new();
+    let output = interpreter.capture_output();
+
+    assert!(interpreter.eval_str("die(\"bye\")\necho 1").is_ok());
+    assert_eq!(interpreter.exit_code(), Some(1));
+    assert_eq!(output.take(), "bye\n");
+
+    assert!(interpreter.eval_str("die()").is_ok());
+    assert_eq!(interpreter.exit_code(), Some(1));
+    assert_eq!(output.take(), "");
+}
+
+#[test]
+fn exit_only_stops_the_script_that_called_it() {
+    let mut interpreter = Interpreter::new();
+    let output = interpreter.capture_output();
+
+    interpreter.eval_str("exit(0)").unwrap();
+    assert_eq!(interpreter.exit_code(), Some(0));
+
+    assert_eq!(interpreter.eval_str("echo 1").ok(), Some(Value::Void));
+    assert_eq!(interpreter.exit_code(), None);
+    assert_eq!(output.take(), "1\n");
+
+    interpreter.eval_str("function stop() { exit(3) }").unwrap();
+    assert!(interpreter.call_function("stop", vec![]).is_ok());
+    assert_eq!(interpreter.exit_code(), Some(3));
+    assert_eq!(interpreter.eval_str("2 + 2").ok(), Some(Value::Number(4.0)));
+}
+
+#[test]
+fn lenient_runtime_errors_evaluate_to_void() {
+    let mut interpreter = Interpreter::new();
+    let output = interpreter.capture_output();
+    interpreter.set_lenient(true);
+
+    let source = "
+echo nope()
+echo arr(1, 2) + 3
+echo arr(1, 2)[5]
+$a = count(5)
+echo $a
+echo \"still running\"";
+    assert_eq!(interpreter.eval_str(source).ok(), Some(Value::Void));
+    assert_eq!(output.take(), "void\nvoid\nvoid\nvoid\nstill running\n");
+
+    // Syntax errors are still reported
+    assert!(interpreter.eval_str("$a = ").is_err());
+
+    interpreter.set_lenient(false);
+    assert!(interpreter.eval_str("echo nope()").is_err());
+}
+
+#[test]
+fn state_carries_over_between_inputs() {
+    // The REPL feeds each input to the same interpreter, like this
+    let mut interpreter = Interpreter::with_filename("<repl>");
+    let output = interpreter.capture_output();
+
+    interpreter.eval_str("$count = 1\n").unwrap();
+    interpreter.eval_str("function bump($by) {\n    return $by + 1\n}\n").unwrap();
+    interpreter.eval_str("$count = bump($count)\n").unwrap();
+    assert_eq!(interpreter.eval_str("$count\n").ok(), Some(Value::Number(2.0)));
+
+    // A failed input leaves what earlier inputs defined alone
+    assert!(interpreter.eval_str("$count = nope()\n").is_err());
+    assert!(interpreter.eval_str("if 1 {\n").is_err());
+    assert_eq!(interpreter.eval_str("bump($count)\n").ok(), Some(Value::Number(3.0)));
+
+    interpreter.eval_str("echo \"count is $count\"\n").unwrap();
+    assert_eq!(output.take(), "count is 2\n");
+}
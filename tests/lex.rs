@@ -0,0 +1,42 @@
+// Tests for telling whether the input to the REPL is complete, through open_blocks()
+
+use phpxx::lex::open_blocks;
+
+#[test]
+fn complete_input_has_nothing_open() {
+    assert_eq!(open_blocks("echo 1\n"), 0);
+    assert_eq!(open_blocks("if $a { echo f(1, arr(2)[0]) }\n"), 0);
+    assert_eq!(open_blocks("echo \"{\" + '(' + \"[\"\n"), 0);
+}
+
+#[test]
+fn unclosed_braces_are_counted() {
+    assert_eq!(open_blocks("function f($a) {\n"), 1);
+    assert_eq!(open_blocks("while 1 {\n    if $a {\n"), 2);
+    assert_eq!(open_blocks("while 1 {\n    if $a {\n    }\n"), 1);
+    assert_eq!(open_blocks("}\n"), -1);
+}
+
+#[test]
+fn unclosed_parentheses_and_brackets_are_counted() {
+    assert_eq!(open_blocks("echo max(1,\n"), 1);
+    assert_eq!(open_blocks("echo max(1,\n    2)\n"), 0);
+    assert_eq!(open_blocks("echo $a[\n"), 1);
+    assert_eq!(open_blocks("echo f(function ($x) {\n"), 2);
+}
+
+#[test]
+fn unclosed_strings_are_counted() {
+    assert_eq!(open_blocks("echo \"hello\n"), 1);
+    assert_eq!(open_blocks("echo \"hello\nworld\"\n"), 0);
+    assert_eq!(open_blocks("echo 'it\\'s\n"), 1);
+    assert_eq!(open_blocks("if 1 {\n    echo \"a\n"), 2);
+}
+
+#[test]
+fn unclosed_heredocs_are_counted() {
+    assert_eq!(open_blocks("echo <<<EOT\n"), 1);
+    assert_eq!(open_blocks("echo <<<EOT\nhello\n"), 1);
+    assert_eq!(open_blocks("echo <<<EOT\nhello\nEOT\n"), 0);
+    assert_eq!(open_blocks("echo f(<<<'EOT'\n    hello\n    EOT)\n"), 0);
+}
@@ -0,0 +1,63 @@
+// Tests for how parsed scripts are shown back by --dump-ast
+
+use phpxx::Interpreter;
+
+fn visualize(source: &str) -> Vec<String> {
+    let mut interpreter = Interpreter::new();
+    let statements = interpreter.parse(source).unwrap();
+    statements.iter().map(|statement| statement.visualize()).collect()
+}
+
+fn jsonify(source: &str) -> Vec<String> {
+    let mut interpreter = Interpreter::new();
+    let statements = interpreter.parse(source).unwrap();
+    statements.iter().map(|statement| statement.jsonify()).collect()
+}
+
+#[test]
+fn math_shows_every_operator_with_its_grouping() {
+    assert_eq!(visualize("echo 1 - 2 / $a"), ["echo ((1 - 2) / $a)"]);
+    assert_eq!(visualize("echo 1 * 2 % 3 ** 4"), ["echo (((1 * 2) % 3) ** 4)"]);
+    assert_eq!(visualize("echo 1 & 2 | 3 << 4"), ["echo ((1 & (2 | 3)) << 4)"]);
+}
+
+#[test]
+fn call_shows_all_of_its_arguments() {
+    assert_eq!(visualize("echo f()"), ["echo f()"]);
+    assert_eq!(visualize("echo max(1, 2, 3)"), ["echo max(1, 2, 3)"]);
+    assert_eq!(visualize("echo f(g($a), \"b\")"), ["echo f(g($a), \"b\")"]);
+}
+
+#[test]
+fn unary_shows_its_operator() {
+    assert_eq!(visualize("echo -$a + !$b"), ["echo ((-$a) + (!$b))"]);
+    assert_eq!(visualize("echo +$a"), ["echo (+$a)"]);
+    assert_eq!(visualize("echo -5"), ["echo -5"]);
+}
+
+#[test]
+fn interpolation_shows_its_parts() {
+    assert_eq!(visualize("echo \"x {$a} y $b\""), ["echo \"x {$a} y {$b}\""]);
+    assert_eq!(visualize("echo \"{$a + 1}!\""), ["echo \"{($a + 1)}!\""]);
+}
+
+#[test]
+fn conditionals_show_their_blocks() {
+    assert_eq!(
+        visualize("if $a { echo 1 } elseif $b { echo 2 } else { echo 3 }"),
+        ["if $a {\n    echo 1\n} else if $b {\n    echo 2\n} else {\n    echo 3\n}"]
+    );
+    assert_eq!(visualize("while $a < 3 { $a++ }"), ["while ($a < 3) {\n    ($a++)\n}"]);
+}
+
+#[test]
+fn json_has_the_type_and_span_of_each_node() {
+    assert_eq!(
+        jsonify("echo 1 - $a"),
+        [concat!(
+            r#"{"type":"Echo","newline":true,"value":"#,
+            r#"{"type":"Math","operator":"-","lhs":{"type":"Number","value":1},"#,
+            r#""rhs":{"type":"Variable","name":"$a"},"span":[7,8]}}"#
+        )]
+    );
+}